@@ -6,6 +6,32 @@
 
 {{readme}}
 
+## Command line
+
+The `slyce` binary applies a slice expression to a JSON array or string read from stdin:
+
+```
+$ cargo install slyce --features cli
+$ echo '[1, "two", [3], {"four": 4}, null]' | slyce '[::-2]'
+[null,[3],1]
+$ echo '"hello"' | slyce '[1:-1:]'
+"ell"
+```
+
+With `--input lines`, `csv` or `bytes` it slices the lines of a text, the rows of a CSV file
+or raw bytes instead, e.g. every 5th of the last 50 lines of a log:
+
+```
+$ slyce --input lines '[-50::5]' < server.log
+```
+
+## Minimum supported Rust version
+
+With its default features slyce builds on Rust 1.57 or newer. Releases up to 0.3.1 supported
+Rust 1.43; the `const fn` slice constructors and `ConstSlice` need the const generics and
+const panics of newer compilers. The optional features depend on other crates, which may
+need a newer compiler still.
+
 ## Development
 
 ### Fuzzing
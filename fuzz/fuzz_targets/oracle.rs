@@ -0,0 +1,14 @@
+#![no_main]
+use libfuzzer_sys::{arbitrary, fuzz_target};
+use slyce::{oracle, Slice};
+
+#[derive(arbitrary::Arbitrary, Debug)]
+struct Input {
+    data: Vec<u8>,
+    slice: Slice,
+}
+
+fuzz_target!(|input: Input| {
+    let r: Vec<&u8> = input.slice.apply(&input.data).collect();
+    assert_eq!(r, oracle::apply(&input.slice, &input.data));
+});
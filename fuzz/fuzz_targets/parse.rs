@@ -0,0 +1,29 @@
+#![no_main]
+use libfuzzer_sys::{arbitrary, fuzz_target};
+use slyce::Slice;
+
+#[derive(arbitrary::Arbitrary, Debug)]
+struct Input {
+    expr: String,
+    slice: Slice,
+}
+
+/// Checks that the displayed slice parses back into an equivalent slice.
+fn check_round_trip(s: &Slice) {
+    let expr = s.to_string();
+    let parsed: Slice = expr.parse().expect("displayed slices parse");
+    assert_eq!(parsed.to_string(), expr);
+    for len in 0..16 {
+        let want: Vec<usize> = s.apply_fn(len, |i| i).collect();
+        let got: Vec<usize> = parsed.apply_fn(len, |i| i).collect();
+        assert_eq!(got, want, "{} on {} elements", expr, len);
+    }
+}
+
+fuzz_target!(|input: Input| {
+    // any string either fails to parse or parses into a slice that round-trips.
+    if let Ok(s) = input.expr.parse::<Slice>() {
+        check_round_trip(&s);
+    }
+    check_round_trip(&input.slice);
+});
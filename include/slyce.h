@@ -0,0 +1,51 @@
+#ifndef SLYCE_H
+#define SLYCE_H
+
+/* Generated with cbindgen from src/ffi.rs; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * A slice has an optional start, an optional end, and an optional step.
+ */
+typedef struct SlyceSlice SlyceSlice;
+
+/**
+ * Parses a NUL-terminated slice expression such as `[1:-1:2]`.
+ *
+ * Returns NULL if `expr` is NULL, not valid UTF-8 or not a valid slice expression. A non-NULL
+ * result must be released with `slyce_free`.
+ *
+ * # Safety
+ *
+ * `expr` must be NULL or point to a NUL-terminated string.
+ */
+SlyceSlice *slyce_parse(const char *expr);
+
+/**
+ * Writes to `out_buf` the indices selected by `slice` in a sequence of `len` elements, in
+ * order, stopping after `out_len` indices.
+ *
+ * Returns the total number of selected indices, which can exceed `out_len`: calling it with a
+ * NULL `out_buf` and a zero `out_len` returns the size of the buffer to allocate.
+ *
+ * # Safety
+ *
+ * `slice` must have been returned by `slyce_parse` and not freed. `out_buf` must be valid for
+ * writing `out_len` elements, unless `out_len` is zero.
+ */
+uintptr_t slyce_indices(const SlyceSlice *slice, uintptr_t len, uintptr_t *out_buf, uintptr_t out_len);
+
+/**
+ * Releases a slice returned by `slyce_parse`. Does nothing if `slice` is NULL.
+ *
+ * # Safety
+ *
+ * `slice` must be NULL or have been returned by `slyce_parse`, and not freed already.
+ */
+void slyce_free(SlyceSlice *slice);
+
+#endif /* SLYCE_H */
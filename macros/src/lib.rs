@@ -0,0 +1,163 @@
+//! Procedural macros for [slyce](https://docs.rs/slyce).
+//!
+//! Use them through the `macros` feature of the `slyce` crate, which re-exports them.
+
+use proc_macro::{Span, TokenStream, TokenTree};
+
+/// Parses a slice expression at compile time and expands to the corresponding `slyce::Slice`.
+///
+/// The expression has the same syntax accepted by `slyce::Slice::from_str`, including the
+/// `start:end` form without a step. Since the expansion only uses `const fn`s, it can initialize
+/// constants.
+///
+/// # Example
+/// ```
+/// use slyce::{Index, Slice};
+/// use slyce_macros::slyce;
+///
+/// const S: Slice = slyce!("[1:-2:2]");
+/// assert_eq!(S.start, Index::Head(1));
+/// assert_eq!(S.end, Index::Tail(2));
+/// assert_eq!(S.step, Some(2));
+///
+/// const T: Slice = slyce!("1:-2");
+/// assert_eq!(T.step, None);
+/// ```
+///
+/// Invalid expressions are reported by the compiler:
+/// ```compile_fail
+/// let s = slyce_macros::slyce!("[1:-2:2:1]");
+/// ```
+#[proc_macro]
+pub fn slyce(input: TokenStream) -> TokenStream {
+    let mut tokens = input.into_iter();
+    let (literal, span) = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(lit)), None) => (lit.to_string(), lit.span()),
+        (Some(tt), _) => return error("expected a string literal", tt.span()),
+        (None, _) => return error("expected a string literal", Span::call_site()),
+    };
+    let expr = match unquote(&literal) {
+        Some(expr) => expr,
+        None => return error("expected a plain string literal", span),
+    };
+    match expand(expr) {
+        Ok(code) => code.parse().unwrap(),
+        Err(msg) => error(&msg, span),
+    }
+}
+
+fn error(msg: &str, span: Span) -> TokenStream {
+    let code: TokenStream = format!("compile_error!({:?})", msg).parse().unwrap();
+    code.into_iter()
+        .map(|mut tt| {
+            tt.set_span(span);
+            tt
+        })
+        .collect()
+}
+
+/// Returns the contents of a string literal without escape sequences.
+fn unquote(literal: &str) -> Option<&str> {
+    let s = literal.strip_prefix('"')?.strip_suffix('"')?;
+    if s.contains('\\') {
+        None
+    } else {
+        Some(s)
+    }
+}
+
+/// Translates a slice expression into the code that builds it.
+fn expand(expr: &str) -> Result<String, String> {
+    let invalid = || format!("invalid slice expression {:?}", expr);
+    let expr = trim_blanks(expr);
+    let inner = match expr.strip_prefix('[') {
+        Some(s) => s.strip_suffix(']').ok_or_else(invalid)?,
+        None => expr,
+    };
+    let parts: Vec<&str> = inner.split(':').map(trim_blanks).collect();
+    if parts.len() != 2 && parts.len() != 3 {
+        return Err(invalid());
+    }
+    let start = index(parts[0]).ok_or_else(invalid)?;
+    let end = index(parts[1]).ok_or_else(invalid)?;
+    let step = match parts.get(2).copied().unwrap_or("") {
+        "" => "::core::option::Option::None".to_string(),
+        s => format!(
+            "::core::option::Option::Some({}isize)",
+            s.parse::<isize>().map_err(|_| invalid())?
+        ),
+    };
+    Ok(format!("::slyce::Slice::new({}, {}, {})", start, end, step))
+}
+
+/// Trims the blanks that `slyce::Slice::from_str` allows around the parts of an expression.
+fn trim_blanks(s: &str) -> &str {
+    s.trim_matches(&[' ', '\t', '\n', '\r'][..])
+}
+
+fn index(s: &str) -> Option<String> {
+    if s.is_empty() {
+        return Some("::slyce::Index::Default".to_string());
+    }
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s),
+    };
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let n = digits.parse::<usize>().ok()?;
+    // `-0` is the same as `0`, like in python.
+    let variant = if negative && n != 0 { "Tail" } else { "Head" };
+    Some(format!("::slyce::Index::{}({}usize)", variant, n))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn expand_valid() {
+        assert_eq!(
+            expand("[1:-2:2]").unwrap(),
+            "::slyce::Slice::new(::slyce::Index::Head(1usize), ::slyce::Index::Tail(2usize), \
+             ::core::option::Option::Some(2isize))"
+        );
+        assert_eq!(
+            expand("[::]").unwrap(),
+            "::slyce::Slice::new(::slyce::Index::Default, ::slyce::Index::Default, \
+             ::core::option::Option::None)"
+        );
+        assert_eq!(
+            expand("[-0::-1]").unwrap(),
+            "::slyce::Slice::new(::slyce::Index::Head(0usize), ::slyce::Index::Default, \
+             ::core::option::Option::Some(-1isize))"
+        );
+        for (lenient, canonical) in &[
+            ("[1:2]", "[1:2:]"),
+            ("1:2", "[1:2:]"),
+            (" [ 1 : -2 : 2 ] ", "[1:-2:2]"),
+            (":", "[::]"),
+        ] {
+            assert_eq!(expand(lenient), expand(canonical), "{:?}", lenient);
+        }
+    }
+
+    #[test]
+    fn expand_invalid() {
+        for expr in &[
+            "",
+            "[1]",
+            "[1:2",
+            "1:2]",
+            "[1 2:3]",
+            "[- 1:2]",
+            "[-:2:3]",
+            "[+1::]",
+            "[1:2:3:4]",
+            "[::x]",
+        ] {
+            assert!(expand(expr).is_err(), "{:?}", expr);
+        }
+    }
+}
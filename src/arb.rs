@@ -0,0 +1,72 @@
+//! `arbitrary` support for indices and slices, used by the fuzz targets.
+
+use crate::{Index, Slice};
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+/// Returns a magnitude biased towards the boundaries that are most likely to expose bugs: small
+/// values, which fall around the length of the sliced input, and values close to the limits of
+/// the integer types.
+fn magnitude(u: &mut Unstructured<'_>) -> Result<usize> {
+    Ok(match u.int_in_range(0u8..=7)? {
+        0..=3 => u.int_in_range(0..=16)?,
+        4 => usize::MAX - u.int_in_range(0..=2)?,
+        5 => isize::MAX as usize - 1 + u.int_in_range(0..=2)?,
+        _ => u.arbitrary()?,
+    })
+}
+
+impl Arbitrary for Index {
+    /// Generates `Default`, `Head(n)` or `Tail(n)`, but never the invalid `Tail(0)`.
+    fn arbitrary(u: &mut Unstructured<'_>) -> Result<Self> {
+        Ok(match u.int_in_range(0u8..=2)? {
+            0 => Index::Default,
+            1 => Index::Head(magnitude(u)?),
+            _ => Index::Tail(magnitude(u)?.max(1)),
+        })
+    }
+}
+
+impl Arbitrary for Slice {
+    fn arbitrary(u: &mut Unstructured<'_>) -> Result<Self> {
+        let start = u.arbitrary()?;
+        let end = u.arbitrary()?;
+        let step = match u.int_in_range(0u8..=7)? {
+            0 => None,
+            1..=4 => Some(u.int_in_range(-4..=4)?),
+            5 => Some(isize::MIN + u.int_in_range(0..=2)?),
+            6 => Some(isize::MAX - u.int_in_range(0..=2)?),
+            _ => Some(u.arbitrary()?),
+        };
+        Ok(Slice { start, end, step })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arbitrary() {
+        // a simple linear congruential generator is enough to cover all the choices.
+        let mut x: u32 = 1;
+        let mut data = [0u8; 1 << 16];
+        for b in data.iter_mut() {
+            x = x.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            *b = (x >> 16) as u8;
+        }
+        let mut u = Unstructured::new(&data);
+
+        let (mut huge, mut small) = (false, false);
+        while let Ok(s) = Slice::arbitrary(&mut u) {
+            if u.is_empty() {
+                break;
+            }
+            for i in &[s.start, s.end] {
+                assert_ne!(*i, Index::Tail(0));
+                huge |= *i == Index::Tail(usize::MAX);
+                small |= *i == Index::Head(1);
+            }
+        }
+        assert!(huge && small);
+    }
+}
@@ -0,0 +1,75 @@
+//! Slicing of Apache Arrow arrays.
+
+use crate::Slice;
+use arrow_array::{Array, ArrayRef, UInt64Array};
+
+impl Slice {
+    /// Returns an array with the elements of `array` that match the slice expression.
+    ///
+    /// When the selected elements are contiguous and in order, i.e. with a step of `1` or when
+    /// at most one element is selected, the result is a zero-copy slice sharing the buffers of
+    /// `array`. Otherwise the selected elements are gathered into new buffers with the `take`
+    /// kernel.
+    ///
+    /// # Example
+    /// ```
+    /// use arrow_array::{Array, Int32Array};
+    /// use slyce::Slice;
+    /// let array = Int32Array::from(vec![1, 2, 3, 4, 5]);
+    ///
+    /// let s: Slice = "[1:-1]".parse()?;
+    /// let r = s.apply_arrow(&array);
+    /// assert_eq!(r.as_any().downcast_ref::<Int32Array>().unwrap().values(), &[2, 3, 4]);
+    ///
+    /// let s: Slice = "[::-2]".parse()?;
+    /// let r = s.apply_arrow(&array);
+    /// assert_eq!(r.as_any().downcast_ref::<Int32Array>().unwrap().values(), &[5, 3, 1]);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn apply_arrow(&self, array: &dyn Array) -> ArrayRef {
+        let it = self.indices(array.len());
+        let n = it.len();
+        if n <= 1 || (it.forward && it.stride == 1) {
+            let offset = if n == 0 { 0 } else { it.next };
+            return array.slice(offset, n);
+        }
+        let indices = UInt64Array::from_iter_values(it.map(|i| i as u64));
+        // the indices are distinct and all within `array`, so the selection can't outgrow it.
+        arrow_select::take::take(array, &indices, None).expect("slice indices are in bounds")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow_array::{Int64Array, StringArray};
+
+    #[test]
+    fn apply_arrow() {
+        // nulls must be carried along with the values.
+        let array = Int64Array::from(vec![Some(0), None, Some(2), Some(3), None, Some(5)]);
+        let sel = |expr: &str| {
+            let r = expr.parse::<Slice>().unwrap().apply_arrow(&array);
+            let r = r.as_any().downcast_ref::<Int64Array>().unwrap();
+            r.iter().collect::<Vec<_>>()
+        };
+        assert_eq!(
+            sel("[::]"),
+            [Some(0), None, Some(2), Some(3), None, Some(5)]
+        );
+        assert_eq!(sel("[1:4:]"), [None, Some(2), Some(3)]);
+        assert_eq!(sel("[::2]"), [Some(0), Some(2), None]);
+        assert_eq!(sel("[::-2]"), [Some(5), Some(3), None]);
+        assert_eq!(sel("[-2:-3:-1]"), [None]);
+        assert_eq!(sel("[9::]"), []);
+        assert_eq!(sel("[::0]"), []);
+
+        let array = StringArray::from(vec!["a", "b", "c", "d"]);
+        let s: Slice = "[1:3]".parse().unwrap();
+        let r = s.apply_arrow(&array);
+        let r = r.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(r.iter().collect::<Vec<_>>(), vec![Some("b"), Some("c")]);
+        // a contiguous selection shares the buffers of the input.
+        assert_eq!(r.values().as_ptr(), array.values().as_ptr());
+    }
+}
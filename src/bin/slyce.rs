@@ -0,0 +1,276 @@
+//! Applies a slice expression to a JSON array or string, the lines of a text, the rows of a CSV
+//! file or the bytes read from stdin.
+//!
+//! ```text
+//! $ echo '[1, "two", [3], {"four": 4}, null]' | slyce '[::-2]'
+//! [null,[3],1]
+//! $ echo '"hello"' | slyce '[1:-1:]'
+//! "ell"
+//! $ slyce --input lines '[-50::5]' < server.log
+//! ```
+//!
+//! Exits with status 1 if the input can't be sliced and with status 2 on usage errors.
+
+use serde_json::Value;
+use slyce::Slice;
+use std::io::{self, BufRead, Read, Write};
+use std::{env, fmt, process};
+
+const USAGE: &str = "usage: slyce [--input json|lines|csv|bytes] <expression> < input
+
+Applies a python-like slice expression, e.g. '[1:-1:2]', to stdin and writes
+the selected parts to stdout.
+
+Input formats:
+  json   (default) a JSON array, sliced by element, or a JSON string, sliced
+         by character; the result is written as JSON
+  lines  lines of text
+  csv    CSV rows; the header row is always written
+  bytes  raw bytes";
+
+/// How stdin is split into the items selected by the slice.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Input {
+    Json,
+    Lines,
+    Csv,
+    Bytes,
+}
+
+impl std::str::FromStr for Input {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Input::Json),
+            "lines" => Ok(Input::Lines),
+            "csv" => Ok(Input::Csv),
+            "bytes" => Ok(Input::Bytes),
+            _ => Err(Error::Usage(format!("unknown input format {:?}", s))),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Error {
+    Usage(String),
+    Io(io::Error),
+    Json(serde_json::Error),
+    Csv(csv::Error),
+    NotSliceable(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Usage(msg) => write!(f, "{}", msg),
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Json(e) => write!(f, "invalid input: {}", e),
+            Error::Csv(e) => write!(f, "invalid input: {}", e),
+            Error::NotSliceable(kind) => {
+                write!(f, "cannot slice {}, expected an array or a string", kind)
+            }
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+impl From<csv::Error> for Error {
+    fn from(e: csv::Error) -> Self {
+        Error::Csv(e)
+    }
+}
+
+fn run<A, R, W>(args: A, input: R, mut output: W) -> Result<(), Error>
+where
+    A: IntoIterator<Item = String>,
+    R: BufRead,
+    W: Write,
+{
+    let (mut format, mut exprs) = (Input::Json, Vec::new());
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                writeln!(output, "{}", USAGE)?;
+                return Ok(());
+            }
+            "-i" | "--input" => match args.next() {
+                Some(f) => format = f.parse()?,
+                None => return Err(Error::Usage(format!("{} requires a value", arg))),
+            },
+            _ => match arg.strip_prefix("--input=") {
+                Some(f) => format = f.parse()?,
+                None => exprs.push(arg),
+            },
+        }
+    }
+    let expr = match exprs.as_slice() {
+        [expr] => expr,
+        _ => return Err(Error::Usage("expected one slice expression".to_string())),
+    };
+    let slice: Slice = expr
+        .parse()
+        .map_err(|e| Error::Usage(format!("invalid expression {:?}: {}", expr, e)))?;
+
+    match format {
+        Input::Json => slice_json(&slice, input, output),
+        Input::Lines => {
+            for line in slice.apply_lines(input) {
+                writeln!(output, "{}", line?)?;
+            }
+            Ok(())
+        }
+        Input::Csv => slice_csv(&slice, input, output),
+        Input::Bytes => {
+            let mut data = Vec::new();
+            let mut input = input;
+            input.read_to_end(&mut data)?;
+            let selected: Vec<u8> = slice.apply(&data).copied().collect();
+            output.write_all(&selected)?;
+            Ok(())
+        }
+    }
+}
+
+fn slice_json<R, W>(slice: &Slice, input: R, mut output: W) -> Result<(), Error>
+where
+    R: Read,
+    W: Write,
+{
+    let value: Value = serde_json::from_reader(input)?;
+    let selected = match value {
+        Value::Array(ref arr) => Value::from(slice.apply(arr).cloned().collect::<Vec<_>>()),
+        Value::String(ref s) => Value::from(slice.apply_to_string(s)),
+        Value::Null => return Err(Error::NotSliceable("null")),
+        Value::Bool(_) => return Err(Error::NotSliceable("a boolean")),
+        Value::Number(_) => return Err(Error::NotSliceable("a number")),
+        Value::Object(_) => return Err(Error::NotSliceable("an object")),
+    };
+    serde_json::to_writer(&mut output, &selected)?;
+    writeln!(output)?;
+    Ok(())
+}
+
+fn slice_csv<R, W>(slice: &Slice, input: R, output: W) -> Result<(), Error>
+where
+    R: Read,
+    W: Write,
+{
+    let mut reader = csv::Reader::from_reader(input);
+    let mut writer = csv::Writer::from_writer(output);
+    writer.write_record(reader.headers()?)?;
+    for record in slice.apply_csv(&mut reader) {
+        writer.write_record(&record?)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn main() {
+    let stdout = io::stdout();
+    let stdin = io::stdin();
+    match run(env::args().skip(1), stdin.lock(), stdout.lock()) {
+        Ok(()) => {}
+        Err(e @ Error::Usage(_)) => {
+            eprintln!("slyce: {}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+        Err(e) => {
+            eprintln!("slyce: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn slyce(args: &[&str], input: &str) -> Result<String, Error> {
+        let mut out = Vec::new();
+        run(
+            args.iter().map(|s| s.to_string()),
+            input.as_bytes(),
+            &mut out,
+        )?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn slice_json() {
+        assert_eq!(slyce(&["[::-2]"], "[1, 2, 3, 4, 5]").unwrap(), "[5,3,1]\n");
+        assert_eq!(slyce(&["[-2::]"], "[1, 2, 3]").unwrap(), "[2,3]\n");
+        assert_eq!(slyce(&["[5::]"], "[]").unwrap(), "[]\n");
+        assert_eq!(
+            slyce(&["[::-2]"], r#"[1, "two", [3], {"four": 4}, null]"#).unwrap(),
+            "[null,[3],1]\n"
+        );
+        assert_eq!(slyce(&["[1::]"], "[[1, [2]], [3]]").unwrap(), "[[3]]\n");
+        assert_eq!(slyce(&["[::-1]"], r#""héllo""#).unwrap(), "\"olléh\"\n");
+        assert_eq!(slyce(&["[9::]"], r#""abc""#).unwrap(), "\"\"\n");
+        assert!(slyce(&["--help"], "").unwrap().starts_with("usage:"));
+    }
+
+    #[test]
+    fn input_formats() {
+        let log: String = (0..100).map(|i| format!("line {}\n", i)).collect();
+        assert_eq!(
+            slyce(&["--input", "lines", "[-50::20]"], &log).unwrap(),
+            "line 50\nline 70\nline 90\n"
+        );
+        assert_eq!(slyce(&["-i", "lines", "[::-1]"], "a\nb").unwrap(), "b\na\n");
+        assert_eq!(
+            slyce(&["--input=csv", "[1::]"], "a,b\n1,2\n\"3,\",4\n").unwrap(),
+            "a,b\n\"3,\",4\n"
+        );
+        assert_eq!(
+            slyce(&["--input", "csv", "[5::]"], "a,b\n1,2\n").unwrap(),
+            "a,b\n"
+        );
+        assert_eq!(
+            slyce(&["--input", "bytes", "[::-2]"], "abcde").unwrap(),
+            "eca"
+        );
+        assert_eq!(
+            slyce(&["--input", "json", "[1::]"], "[1,2]").unwrap(),
+            "[2]\n"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(slyce(&[], "[]"), Err(Error::Usage(_))));
+        assert!(matches!(
+            slyce(&["[::]", "[::]"], "[]"),
+            Err(Error::Usage(_))
+        ));
+        assert!(matches!(slyce(&["[::"], "[]"), Err(Error::Usage(_))));
+        assert!(matches!(slyce(&["--input"], "[]"), Err(Error::Usage(_))));
+        assert!(matches!(
+            slyce(&["-i", "xml", "[::]"], ""),
+            Err(Error::Usage(_))
+        ));
+        assert!(matches!(
+            slyce(&["-i", "csv", "[-1::]"], "a,b\n1,2\n3\n"),
+            Err(Error::Csv(_))
+        ));
+        assert!(matches!(slyce(&["[::]"], "[1,"), Err(Error::Json(_))));
+        assert!(matches!(
+            slyce(&["[::]"], "{}"),
+            Err(Error::NotSliceable(_))
+        ));
+        assert!(matches!(slyce(&["[::]"], "1"), Err(Error::NotSliceable(_))));
+    }
+}
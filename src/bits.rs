@@ -0,0 +1,46 @@
+//! Slicing of `bitvec` bit slices.
+
+use crate::Slice;
+use bitvec::order::BitOrder;
+use bitvec::slice::BitSlice;
+use bitvec::store::BitStore;
+use bitvec::vec::BitVec;
+
+impl Slice {
+    /// Returns an iterator that yields the bits of `bits` that match the slice expression.
+    pub fn apply_bits<'a, T, O>(&self, bits: &'a BitSlice<T, O>) -> impl Iterator<Item = bool> + 'a
+    where
+        T: BitStore,
+        O: BitOrder,
+    {
+        self.indices(bits.len()).map(move |i| bits[i])
+    }
+
+    /// Returns a new bit vector with the bits of `bits` that match the slice expression.
+    pub fn apply_to_bitvec<T, O>(&self, bits: &BitSlice<T, O>) -> BitVec<T, O>
+    where
+        T: BitStore,
+        O: BitOrder,
+    {
+        self.apply_bits(bits).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bitvec::prelude::*;
+
+    #[test]
+    fn apply_bits() {
+        let bits = bits![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 0, 1];
+        let sel = |expr: &str| expr.parse::<Slice>().unwrap().apply_to_bitvec(bits);
+        assert_eq!(sel("[-7::]"), bits![1, 1, 0, 0, 1, 0, 1]);
+        assert_eq!(sel("[::2]"), bits![1, 1, 0, 1, 1]);
+        assert_eq!(sel("[::-3]"), bits![1, 0, 1]);
+        assert!(sel("[::0]").is_empty());
+
+        let s: Slice = "[1:3:]".parse().unwrap();
+        assert_eq!(s.apply_bits(bits).collect::<Vec<_>>(), [false, true]);
+    }
+}
@@ -0,0 +1,42 @@
+//! Slicing of `bytes::Bytes` buffers.
+
+use crate::Slice;
+use alloc::vec::Vec;
+use bytes::Bytes;
+
+impl Slice {
+    /// Returns the bytes of `b` that match the slice expression.
+    ///
+    /// When the selected bytes are contiguous and in order, i.e. with a step of `1` or when at
+    /// most one byte is selected, the result shares the buffer of `b` without copying it.
+    /// Otherwise the selected bytes are copied into a new buffer.
+    pub fn slice_bytes(&self, b: &Bytes) -> Bytes {
+        let n = self.count(b.len());
+        match self.nth(b.len(), 0) {
+            None => Bytes::new(),
+            Some(first) if n == 1 || self.step_or_default() == 1 => b.slice(first..first + n),
+            Some(_) => self.apply(b).copied().collect::<Vec<u8>>().into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn slice_bytes() {
+        let b = Bytes::from_static(b"hello world");
+        let sel = |expr: &str| expr.parse::<Slice>().unwrap().slice_bytes(&b);
+
+        let shared = sel("[-5::]");
+        assert_eq!(shared, "world");
+        assert_eq!(shared.as_ptr(), b[6..].as_ptr());
+        assert_eq!(sel("[4:3:-1]").as_ptr(), b[4..].as_ptr());
+
+        assert_eq!(sel("[::-1]"), "dlrow olleh");
+        assert_eq!(sel("[::2]"), "hlowrd");
+        assert_eq!(sel("[5:5:]"), "");
+        assert_eq!(sel("[::0]"), "");
+    }
+}
@@ -0,0 +1,447 @@
+//! Combinators that rewrite a slice into another slice, and generators of slices, so that
+//! selections built step by step stay a single `Slice` that can be displayed, parsed back and
+//! applied later.
+
+use crate::{Index, Slice};
+use core::convert::TryFrom;
+
+impl Slice {
+    /// Returns the slice that selects every `n`-th element selected by this slice, starting from
+    /// the first one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let s: Slice = "[1::2]".parse()?;
+    /// assert_eq!(s.every(3).to_string(), "[1::6]");
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn every(&self, n: usize) -> Slice {
+        assert!(n != 0, "every(0) would never advance");
+        let n = isize::try_from(n).unwrap_or(isize::MAX);
+        Slice {
+            step: Some(self.step_or_default().saturating_mul(n)),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the slice that selects the first `n` elements selected by this slice, in arrays
+    /// of any length.
+    ///
+    /// This is always possible when the start is relative to the side of the array the slice
+    /// iterates from: the start of the array with a positive step, or its end with a negative
+    /// one (omitted starts are). Otherwise the position of the start depends on whether it's
+    /// clamped to the array, and so on its length, and `None` is returned unless `n` elements
+    /// cover the whole selection. `None` is also returned when the end would be the nearest
+    /// of a bound relative to the start of the array and one relative to its end.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let s: Slice = "[::-1]".parse()?;
+    /// assert_eq!(s.take_first(3).unwrap().to_string(), "[:-4:-1]");
+    /// let s: Slice = "[2:]".parse()?;
+    /// assert_eq!(s.take_first(3).unwrap().to_string(), "[2:5:]");
+    /// assert!("[2:-1]".parse::<Slice>()?.take_first(3).is_none());
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn take_first(&self, n: usize) -> Option<Slice> {
+        let step = self.step_or_default();
+        if n == 0 {
+            return Some(Slice::new(Index::Head(0), Index::Head(0), None));
+        }
+        if step == 0 {
+            return Some(self.clone());
+        }
+        let (forward, stride) = (step > 0, step.unsigned_abs());
+        let span = n.saturating_mul(stride);
+        let end = match (forward, self.anchored_start(forward)) {
+            (true, Index::Head(a)) => Index::Head(a.saturating_add(span)),
+            (false, Index::Tail(a)) => Index::Tail(a.saturating_add(span)),
+            // wherever the start is clamped to, at most ceil(a / stride) elements follow it.
+            (true, Index::Tail(a)) if span >= a => return Some(self.clone()),
+            // wherever the start is clamped to, at most a / stride + 1 elements follow it.
+            (false, Index::Head(a)) if span > a => return Some(self.clone()),
+            _ => return None,
+        };
+        let end = match (&self.end, end) {
+            (Index::Default, end) => end,
+            (Index::Tail(0), end) if forward => end,
+            // the nearest of two ends of the same kind is the one with the lowest magnitude.
+            (&Index::Head(b), Index::Head(e)) => Index::Head(b.min(e)),
+            (&Index::Tail(b), Index::Tail(e)) => Index::Tail(b.min(e)),
+            // the nearest of a head and a tail bound depends on the length.
+            _ => return None,
+        };
+        Some(Slice::new(self.start.clone(), end, self.step))
+    }
+
+    /// Returns the slice that selects all but the first `n` elements selected by this slice, in
+    /// arrays of any length.
+    ///
+    /// Like `take_first`, this is possible when the start is relative to the side of the array
+    /// the slice iterates from; otherwise `None` is returned, unless `n` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let s: Slice = "[1:-1:2]".parse()?;
+    /// assert_eq!(s.skip_first(2).unwrap().to_string(), "[5:-1:2]");
+    /// let s: Slice = "[::-1]".parse()?;
+    /// assert_eq!(s.skip_first(2).unwrap().to_string(), "[-3::-1]");
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn skip_first(&self, n: usize) -> Option<Slice> {
+        let step = self.step_or_default();
+        if n == 0 || step == 0 {
+            return Some(self.clone());
+        }
+        let forward = step > 0;
+        let span = n.saturating_mul(step.unsigned_abs());
+        let start = match (forward, self.anchored_start(forward)) {
+            (true, Index::Head(a)) => Index::Head(a.saturating_add(span)),
+            (false, Index::Tail(a)) => Index::Tail(a.saturating_add(span)),
+            _ => return None,
+        };
+        Some(Slice::new(start, self.end.clone(), self.step))
+    }
+
+    /// Returns the slice that selects the last `n` elements of an array, i.e. `[-n:]`.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// assert_eq!(Slice::last_n(2).apply(&[1, 2, 3]).collect::<Vec<_>>(), vec![&2, &3]);
+    /// assert_eq!(Slice::last_n(5).apply(&[1, 2, 3]).count(), 3);
+    /// ```
+    pub const fn last_n(n: usize) -> Slice {
+        Slice::new(Index::Tail(n), Index::Default, None)
+    }
+
+    /// Returns the start of the slice, replacing an omitted start with the index it stands for,
+    /// relative to the side of the array the slice iterates from.
+    fn anchored_start(&self, forward: bool) -> Index {
+        match (&self.start, forward) {
+            (Index::Default, true) => Index::Head(0),
+            // the end of the array is clamped to the last element when iterating backward.
+            (Index::Default, false) | (Index::Tail(0), false) => Index::Tail(1),
+            (i, _) => i.clone(),
+        }
+    }
+
+    /// Returns the slice whose bounds are the ones of this slice moved by `offset`, towards the
+    /// end of the array if it's positive.
+    ///
+    /// Bounds keep their kind: a bound relative to the end moves relative to the end. Omitted
+    /// bounds are replaced by the explicit bounds they default to. Bounds are moved before being
+    /// clamped to the array, so positions outside of it can be shifted into it. A bound relative
+    /// to the end can't move past the end of the array, so it saturates there like
+    /// `Index + usize`; with a step other than 1 or -1 this can change which of the shifted
+    /// positions are selected.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let s: Slice = "[2:-3:]".parse()?;
+    /// assert_eq!(s.shift(-2).to_string(), "[0:-5:]");
+    /// assert_eq!(s.shift(2).to_string(), "[4:-1:]");
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn shift(&self, offset: isize) -> Slice {
+        let forward = self.step_or_default() >= 0;
+        let start = match (&self.start, forward) {
+            (Index::Default, true) => Index::Head(0),
+            (Index::Default, false) => Index::Tail(1),
+            (i, _) => i.clone(),
+        };
+        let end = match (&self.end, forward) {
+            (Index::Default, true) => Index::Tail(0).offset_by(offset),
+            // the bound before the first element is only reachable by moving a head bound.
+            (Index::Default, false) if offset > 0 => Index::Head(offset as usize - 1),
+            (Index::Default, false) => Index::Default,
+            (i, _) => i.clone().offset_by(offset),
+        };
+        Slice::new(start.offset_by(offset), end, self.step)
+    }
+
+    /// Returns the slice that selects, in an array `factor` times longer, the first of the
+    /// `factor` positions each position selected by this slice maps to, e.g. the first sample
+    /// of each selected frame. Both the bounds and the step are multiplied by `factor`.
+    ///
+    /// Bounds are scaled before being clamped to the array: a backward start past the last
+    /// element ends up on the last position of the longer array rather than on the first
+    /// position of the last frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let frames: Slice = "[1:-1:2]".parse()?;
+    /// assert_eq!(frames.scale(4).to_string(), "[4:-4:8]");
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn scale(&self, factor: usize) -> Slice {
+        assert!(factor != 0, "scale(0) would collapse the selection");
+        let scale = |i: &Index| match *i {
+            Index::Head(n) => Index::Head(n.saturating_mul(factor)),
+            Index::Tail(n) => Index::Tail(n.saturating_mul(factor)),
+            Index::Default => Index::Default,
+        };
+        let start = match (&self.start, self.step_or_default() >= 0) {
+            // the backward default starts at the last element, which is `factor` from the end.
+            (Index::Default, false) => Index::Tail(factor),
+            (i, _) => scale(i),
+        };
+        let factor = isize::try_from(factor).unwrap_or(isize::MAX);
+        let step = self.step.map(|s| s.saturating_mul(factor)).or(Some(factor));
+        Slice::new(start, scale(&self.end), step)
+    }
+
+    /// Splits the selection at `pivot`, returning the slice that selects the elements coming
+    /// before the pivot in selection order, and the slice that selects the rest, in arrays of
+    /// any length.
+    ///
+    /// The pivot is used like the end of the slice: the first slice is what this slice would
+    /// select if it ended at `pivot` instead. Both keep the step of this slice, so together
+    /// they select the same elements in the same order.
+    ///
+    /// Like `skip_first`, this needs the start to be relative to the side of the array the
+    /// slice iterates from, and the pivot to be relative to the same side as the start and as
+    /// the end; otherwise which elements come before the pivot depends on the length of the
+    /// array and `None` is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::{Index, Slice};
+    /// let s: Slice = "[1::2]".parse()?;
+    /// let (prefix, suffix) = s.split_at(Index::Head(4)).unwrap();
+    /// assert_eq!(prefix.to_string(), "[1:4:2]");
+    /// assert_eq!(suffix.to_string(), "[5::2]");
+    /// assert!(s.split_at(Index::Tail(4)).is_none());
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn split_at(&self, pivot: Index) -> Option<(Slice, Slice)> {
+        let step = self.step_or_default();
+        let forward = step > 0;
+        let empty = Slice::new(Index::Head(0), Index::Head(0), None);
+        if step == 0 || pivot == Index::Default || (forward && pivot == Index::Tail(0)) {
+            return Some((self.clone(), empty));
+        }
+        let stride = step.unsigned_abs();
+        // the first position the slice steps on at or past `p`, stepping from `a` to `p`.
+        let cross = |a: usize, p: usize| {
+            let steps = (p - a) / stride + usize::from((p - a) % stride != 0);
+            a.saturating_add(steps.saturating_mul(stride))
+        };
+        let start = match (forward, self.anchored_start(forward), &pivot) {
+            (true, Index::Head(a), &Index::Head(p)) if p > a => Index::Head(cross(a, p)),
+            (false, Index::Tail(a), &Index::Tail(p)) if p > a => Index::Tail(cross(a, p)),
+            // the pivot doesn't come after the start, so nothing comes before it.
+            (true, Index::Head(_), Index::Head(_)) | (false, Index::Tail(_), Index::Tail(_)) => {
+                return Some((empty, self.clone()))
+            }
+            _ => return None,
+        };
+        let end = match (&self.end, &pivot) {
+            (Index::Default, p) => p.clone(),
+            (Index::Tail(0), p) if forward => p.clone(),
+            // the nearest of the end and the pivot, which must be of the same kind.
+            (&Index::Head(e), &Index::Head(p)) => {
+                Index::Head(if forward { e.min(p) } else { e.max(p) })
+            }
+            (&Index::Tail(e), &Index::Tail(p)) => {
+                Index::Tail(if forward { e.max(p) } else { e.min(p) })
+            }
+            _ => return None,
+        };
+        Some((
+            Slice::new(self.start.clone(), end, self.step),
+            Slice::new(start, self.end.clone(), self.step),
+        ))
+    }
+
+    /// Returns the slices that partition an array of length `len` into consecutive pages of
+    /// `page_size` elements; the last page is shorter if `page_size` doesn't divide `len`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `page_size` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let pages: Vec<String> = Slice::pages(5, 2).map(|s| s.to_string()).collect();
+    /// assert_eq!(pages, vec!["[0:2:]", "[2:4:]", "[4:5:]"]);
+    /// ```
+    pub fn pages(len: usize, page_size: usize) -> impl ExactSizeIterator<Item = Slice> {
+        assert!(page_size != 0, "pages must not be empty");
+        (0..len).step_by(page_size).map(move |start| {
+            let end = len.min(start.saturating_add(page_size));
+            Slice::new(Index::Head(start), Index::Head(end), None)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn combinators() {
+        let parse = |expr: &str| expr.parse::<Slice>().unwrap();
+        let take = |expr, n| parse(expr).take_first(n).map(|s| s.to_string());
+        assert_eq!(take("[::]", 3).as_deref(), Some("[:3:]"));
+        assert_eq!(take("[1::2]", 3).as_deref(), Some("[1:7:2]"));
+        assert_eq!(take("[1:3:]", 5).as_deref(), Some("[1:3:]"));
+        assert_eq!(take("[::-1]", 3).as_deref(), Some("[:-4:-1]"));
+        assert_eq!(take("[-2:-9:-1]", 3).as_deref(), Some("[-2:-5:-1]"));
+        assert_eq!(take("[::2]", 0).as_deref(), Some("[0:0:]"));
+        assert_eq!(take("[::0]", 3).as_deref(), Some("[::0]"));
+        // starts clamped to the array: enough elements cover the whole selection.
+        assert_eq!(take("[-2:]", 3).as_deref(), Some("[-2::]"));
+        assert_eq!(take("[1::-1]", 5).as_deref(), Some("[1::-1]"));
+        assert_eq!(take("[-5:]", 3), None);
+        assert_eq!(take("[5::-1]", 3), None);
+        // the nearest end depends on the length.
+        assert_eq!(take("[2:-1]", 3), None);
+
+        let skip = |expr, n| parse(expr).skip_first(n).map(|s| s.to_string());
+        assert_eq!(skip("[1:-1:2]", 2).as_deref(), Some("[5:-1:2]"));
+        assert_eq!(skip("[::-1]", 2).as_deref(), Some("[-3::-1]"));
+        assert_eq!(skip("[-3:]", 0).as_deref(), Some("[-3::]"));
+        assert_eq!(skip("[::0]", 2).as_deref(), Some("[::0]"));
+        assert_eq!(skip("[-3:]", 1), None);
+        assert_eq!(skip("[2::-1]", 1), None);
+
+        let every = |expr, n| parse(expr).every(n).to_string();
+        assert_eq!(every("[1::2]", 3), "[1::6]");
+        assert_eq!(every("[::-1]", 2), "[::-2]");
+        assert_eq!(every("[3:]", 1), "[3::1]");
+
+        let v = [1, 2, 3];
+        let last = |n| Slice::last_n(n).apply(&v).copied().collect::<Vec<_>>();
+        assert_eq!(last(2), [2, 3]);
+        assert_eq!(last(5), [1, 2, 3]);
+        assert_eq!(last(0), [0; 0]);
+    }
+
+    #[test]
+    fn shift_and_scale() {
+        for len in 0..8 {
+            for start in -9..9 {
+                for end in -9..9 {
+                    for step in (-4..4).filter(|&s| s != 0) {
+                        let s = Slice::new(start.into(), end.into(), Some(step));
+                        let selected: Vec<usize> = s.indices(len).collect();
+                        // the positions of the bounds, unclamped.
+                        let pos = |i: &Index, default: isize| match *i {
+                            Index::Head(n) => n as isize,
+                            Index::Tail(n) => len as isize - n as isize,
+                            Index::Default => default,
+                        };
+                        let (lo, hi) = if step > 0 {
+                            (pos(&s.start, 0), pos(&s.end, len as isize))
+                        } else {
+                            (pos(&s.end, -1) + 1, pos(&s.start, len as isize - 1) + 1)
+                        };
+                        for offset in (-9..9).filter(|_| step.abs() == 1) {
+                            let mut want: Vec<usize> = (0..len)
+                                .filter(|&i| (lo..hi).contains(&(i as isize - offset)))
+                                .collect();
+                            if step < 0 {
+                                want.reverse();
+                            }
+                            let got: Vec<usize> = s.shift(offset).indices(len).collect();
+                            assert_eq!(got, want, "{} by {} on {}", s, offset, len);
+                        }
+                        let past_end = step < 0
+                            && (s.start == Index::Tail(0)
+                                || matches!(s.start, Index::Head(n) if n >= len));
+                        for factor in (1..4).filter(|_| !past_end) {
+                            let want: Vec<usize> = selected.iter().map(|i| i * factor).collect();
+                            let got: Vec<usize> = s.scale(factor).indices(len * factor).collect();
+                            assert_eq!(got, want, "{} by {} on {}", s, factor, len);
+                        }
+                    }
+                }
+            }
+        }
+
+        // head bounds keep the phase of the step.
+        let s: Slice = "[1:6:-3]".parse().unwrap();
+        assert_eq!(s.shift(2).to_string(), "[3:8:-3]");
+        // an omitted end moves with the start.
+        let s: Slice = "[-3:]".parse().unwrap();
+        assert_eq!(s.shift(-2).to_string(), "[-5:-2:]");
+        let s = Slice::new(Index::Default, Index::Default, None);
+        assert_eq!(s.scale(3).to_string(), "[::3]");
+    }
+
+    #[test]
+    fn split_at() {
+        let split = |expr: &str, pivot| {
+            let (prefix, suffix) = expr.parse::<Slice>().unwrap().split_at(pivot)?;
+            Some((prefix.to_string(), suffix.to_string()))
+        };
+        let halves = |prefix: &str, suffix: &str| Some((prefix.to_string(), suffix.to_string()));
+        assert_eq!(split("[1::2]", Index::Head(4)), halves("[1:4:2]", "[5::2]"));
+        assert_eq!(split("[::]", Index::Head(3)), halves("[:3:]", "[3::]"));
+        assert_eq!(
+            split("[::-2]", Index::Tail(4)),
+            halves("[:-4:-2]", "[-5::-2]")
+        );
+        assert_eq!(
+            split("[-2:-9:-1]", Index::Tail(5)),
+            halves("[-2:-5:-1]", "[-5:-9:-1]")
+        );
+        // pivots past the end or before the start.
+        assert_eq!(
+            split("[2:8:]", Index::Head(10)),
+            halves("[2:8:]", "[10:8:]")
+        );
+        assert_eq!(split("[5:]", Index::Head(2)), halves("[0:0:]", "[5::]"));
+        assert_eq!(
+            split("[1:-1:]", Index::Default),
+            halves("[1:-1:]", "[0:0:]")
+        );
+        assert_eq!(split("[::0]", Index::Head(1)), halves("[::0]", "[0:0:]"));
+        // the halves depend on the length.
+        assert_eq!(split("[-3:]", Index::Tail(1)), None);
+        assert_eq!(split("[1::2]", Index::Tail(4)), None);
+        assert_eq!(split("[1:-1:]", Index::Head(3)), None);
+
+        let v: Vec<usize> = (0..10).collect();
+        let s: Slice = "[::-3]".parse().unwrap();
+        let (prefix, suffix) = s.split_at(Index::Tail(5)).unwrap();
+        assert_eq!(prefix.apply(&v).copied().collect::<Vec<_>>(), [9, 6]);
+        assert_eq!(suffix.apply(&v).copied().collect::<Vec<_>>(), [3, 0]);
+    }
+
+    #[test]
+    fn pages() {
+        for len in 0..20 {
+            for page_size in 1..8 {
+                let v: Vec<usize> = (0..len).collect();
+                let pages = Slice::pages(len, page_size);
+                assert_eq!(pages.len(), (len + page_size - 1) / page_size);
+                let got: Vec<Vec<usize>> = pages.map(|s| s.apply(&v).copied().collect()).collect();
+                let want: Vec<Vec<usize>> = v.chunks(page_size).map(<[usize]>::to_vec).collect();
+                assert_eq!(got, want, "{} by {}", len, page_size);
+            }
+        }
+        assert_eq!(Slice::pages(usize::MAX, usize::MAX / 2 + 1).len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn every_zero() {
+        Slice::new(Index::Default, Index::Default, None).every(0);
+    }
+}
@@ -0,0 +1,105 @@
+//! Slices known at compile time.
+
+use crate::{Index, Slice};
+
+/// The value that stands for `Index::Default` in the parameters of a `ConstSlice`.
+///
+/// This means that `isize::MIN` cannot be used as a `ConstSlice` index.
+pub const DEFAULT: isize = isize::MIN;
+
+/// A slice whose start, end and step are known at compile time.
+///
+/// Indices follow the same convention as `From<isize> for Index`, with `DEFAULT` standing for
+/// `Index::Default`. Applying a `ConstSlice` to a fixed-size array produces a fixed-size array,
+/// whose length is checked by the compiler.
+///
+/// # Example
+/// ```
+/// use slyce::{ConstSlice, DEFAULT};
+///
+/// type EveryOther = ConstSlice<1, DEFAULT, 2>;
+///
+/// let arr = [10, 20, 30, 40, 50];
+/// let out: [&i32; 2] = EveryOther::apply(&arr);
+/// assert_eq!(out, [&20, &40]);
+/// ```
+///
+/// A mismatched output length is a compile-time error:
+/// ```compile_fail
+/// use slyce::{ConstSlice, DEFAULT};
+///
+/// let out: [&i32; 3] = ConstSlice::<1, DEFAULT, 2>::apply(&[10, 20, 30, 40, 50]);
+/// ```
+pub struct ConstSlice<const START: isize, const END: isize, const STEP: isize>;
+
+impl<const START: isize, const END: isize, const STEP: isize> ConstSlice<START, END, STEP> {
+    /// The runtime equivalent of this slice.
+    pub const SLICE: Slice = Slice::new(index(START), index(END), Some(STEP));
+
+    /// Returns the number of elements this slice selects in an array of length `len`.
+    pub const fn count(len: usize) -> usize {
+        Self::SLICE.count(len)
+    }
+
+    /// Returns an array with references to the elements that match the slice expression.
+    ///
+    /// `M` must be equal to `Self::count(N)`; this is checked at compile time.
+    pub fn apply<T, const N: usize, const M: usize>(arr: &[T; N]) -> [&T; M] {
+        #[allow(clippy::let_unit_value)]
+        let () = LengthCheck::<START, END, STEP, N, M>::OK;
+
+        let mut indices = Self::SLICE.indices(N);
+        [(); M].map(|()| match indices.next() {
+            Some(i) => &arr[i],
+            None => unreachable!(),
+        })
+    }
+}
+
+const fn index(i: isize) -> Index {
+    if i == DEFAULT {
+        Index::Default
+    } else {
+        Index::from_isize(i)
+    }
+}
+
+struct LengthCheck<
+    const START: isize,
+    const END: isize,
+    const STEP: isize,
+    const N: usize,
+    const M: usize,
+>;
+
+impl<const START: isize, const END: isize, const STEP: isize, const N: usize, const M: usize>
+    LengthCheck<START, END, STEP, N, M>
+{
+    const OK: () = assert!(
+        ConstSlice::<START, END, STEP>::count(N) == M,
+        "the output array length doesn't match the number of selected elements"
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn apply() {
+        let arr = [10, 20, 30, 40, 50];
+
+        let out: [&i32; 5] = ConstSlice::<DEFAULT, DEFAULT, 1>::apply(&arr);
+        assert_eq!(out, [&10, &20, &30, &40, &50]);
+
+        let out: [&i32; 3] = ConstSlice::<-1, DEFAULT, -2>::apply(&arr);
+        assert_eq!(out, [&50, &30, &10]);
+
+        let out: [&i32; 0] = ConstSlice::<3, 1, 1>::apply(&arr);
+        assert_eq!(out, [] as [&i32; 0]);
+
+        const N: usize = ConstSlice::<1, -1, 1>::count(5);
+        let out: [&i32; N] = ConstSlice::<1, -1, 1>::apply(&arr);
+        assert_eq!(out, [&20, &30, &40]);
+    }
+}
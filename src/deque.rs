@@ -0,0 +1,51 @@
+//! Slicing of `VecDeque`s.
+
+use crate::Slice;
+use alloc::collections::VecDeque;
+
+impl Slice {
+    /// Returns an iterator that yields the elements of a `VecDeque` that match the slice
+    /// expression.
+    ///
+    /// The elements are read directly from the two contiguous halves returned by
+    /// `VecDeque::as_slices`, without making the deque contiguous or copying it.
+    pub fn apply_deque<'a, T>(&self, d: &'a VecDeque<T>) -> impl Iterator<Item = &'a T> + 'a {
+        let (front, back) = d.as_slices();
+        self.indices(d.len()).map(move |i| match front.get(i) {
+            Some(x) => x,
+            None => &back[i - front.len()],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn apply_deque() {
+        // a ring buffer that has wrapped around, so that both halves are non-empty.
+        let mut d = VecDeque::with_capacity(4);
+        for i in 0..10 {
+            if d.len() == 4 {
+                d.pop_front();
+            }
+            d.push_back(i);
+        }
+        assert!(!d.as_slices().1.is_empty());
+        let sel = |expr: &str| {
+            let s: Slice = expr.parse().unwrap();
+            s.apply_deque(&d).copied().collect::<Vec<_>>()
+        };
+        assert_eq!(sel("[::]"), [6, 7, 8, 9]);
+        assert_eq!(sel("[1:-1:]"), [7, 8]);
+        assert_eq!(sel("[::-1]"), [9, 8, 7, 6]);
+        assert_eq!(sel("[-1::-3]"), [9, 6]);
+        assert_eq!(sel("[3:9:]"), [9]);
+        assert_eq!(sel("[4::]"), [0; 0]);
+        assert_eq!(sel("[::0]"), [0; 0]);
+        let s: Slice = "[::-1]".parse().unwrap();
+        assert_eq!(s.apply_deque(&VecDeque::<i32>::new()).count(), 0);
+    }
+}
@@ -0,0 +1,74 @@
+use core::fmt;
+
+/// The error type for slice operations that can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The number of provided elements doesn't match the number of selected elements.
+    LengthMismatch {
+        /// Number of elements selected by the slice.
+        expected: usize,
+        /// Number of elements provided.
+        actual: usize,
+    },
+    /// Two slices that must be disjoint select the same element.
+    Overlap {
+        /// Position of the first element selected by both slices.
+        index: usize,
+    },
+    /// The magnitude of an integer doesn't fit in a `usize`, so it can't be an index.
+    Overflow,
+    /// The step of the slice can't be expressed in the target syntax.
+    UnsupportedStep {
+        /// The step of the slice.
+        step: isize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::LengthMismatch { expected, actual } => write!(
+                f,
+                "length mismatch: expected {} elements, found {}",
+                expected, actual
+            ),
+            Error::Overlap { index } => write!(f, "slices overlap at index {}", index),
+            Error::Overflow => write!(f, "integer too large for an index"),
+            Error::UnsupportedStep { step } => write!(f, "unsupported step {}", step),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Why a slice selects no element of an array.
+///
+/// This is returned by `Slice::try_apply` and `Slice::empty_reason`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmptyReason {
+    /// The step is zero.
+    ZeroStep,
+    /// The array is empty.
+    EmptyInput,
+    /// The start lies past the last element in the direction of the iteration, e.g. `[7:]` on
+    /// an array of 5 elements, or `[-7::-1]`.
+    StartOutOfBounds,
+    /// The end doesn't come after the start in the direction of the iteration, e.g. `[3:1]` or
+    /// `[1:3:-1]`.
+    EndBeforeStart,
+}
+
+impl fmt::Display for EmptyReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmptyReason::ZeroStep => write!(f, "the step is zero"),
+            EmptyReason::EmptyInput => write!(f, "the input is empty"),
+            EmptyReason::StartOutOfBounds => write!(f, "the start is past the end of the input"),
+            EmptyReason::EndBeforeStart => write!(f, "the end doesn't come after the start"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EmptyReason {}
@@ -0,0 +1,213 @@
+//! Extension traits that let containers be sliced directly.
+
+use crate::{Index, Iter, Slice};
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+/// Extension methods for slicing arrays, `Vec`s and anything else that derefs to `[T]`.
+///
+/// # Example
+/// ```
+/// use slyce::SliceExt;
+/// let v = vec![10, 20, 30, 40, 50];
+/// assert_eq!(v.slyce("[1:-1:2]")?, vec![&20, &40]);
+/// # Ok::<(), slyce::ParseError>(())
+/// ```
+pub trait SliceExt<T> {
+    /// Returns the elements selected by `slice`, which can be a `Slice` or a slice expression.
+    fn slyce<S>(&self, slice: S) -> Result<Vec<&T>, S::Error>
+    where
+        S: TryInto<Slice>;
+
+    /// Returns the elements selected by `slice`, or `None` if any of its indices falls outside
+    /// of the array, like `slice::get` with a range.
+    ///
+    /// `slyce` silently clamps such indices to the array, as python does; this tells an exact
+    /// selection apart from a clamped one. An index is outside of the array if `Index::to_bound`
+    /// would have to clamp it: with a positive step the bounds can be anywhere from `-len` to
+    /// `len`, with a negative one from `-len - 1` to `len - 1`.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::{Slice, SliceExt};
+    /// let v = vec![10, 20, 30, 40, 50];
+    /// assert_eq!(v.get_slice(&"[1:5]".parse()?), Some(vec![&20, &30, &40, &50]));
+    /// assert_eq!(v.get_slice(&"[1:6]".parse()?), None);
+    /// assert_eq!(v.get_slice(&"[-6:]".parse()?), None);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    fn get_slice(&self, slice: &Slice) -> Option<Vec<&T>>;
+}
+
+impl<T> SliceExt<T> for [T] {
+    fn slyce<S>(&self, slice: S) -> Result<Vec<&T>, S::Error>
+    where
+        S: TryInto<Slice>,
+    {
+        Ok(slice.try_into()?.apply_to_vec(self))
+    }
+
+    fn get_slice(&self, slice: &Slice) -> Option<Vec<&T>> {
+        let (len, forward) = (self.len(), slice.step_or_default() >= 0);
+        if in_bounds(&slice.start, len, forward) && in_bounds(&slice.end, len, forward) {
+            Some(slice.apply_to_vec(self))
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns true if `Index::to_bound` doesn't need to clamp `i` to an array of length `len`.
+fn in_bounds(i: &Index, len: usize, forward: bool) -> bool {
+    match (i, forward) {
+        (&Index::Head(n), true) => n <= len,
+        (&Index::Head(n), false) => n < len,
+        (&Index::Tail(n), true) => n <= len,
+        (&Index::Tail(n), false) => n >= 1 && n - 1 <= len,
+        (Index::Default, _) => true,
+    }
+}
+
+/// Extension methods for slicing iterators of known length without collecting them first.
+pub trait IteratorSliceExt: ExactSizeIterator + Sized {
+    /// Returns an iterator adaptor that yields the items selected by `slice`.
+    ///
+    /// Items are pulled from the underlying iterator lazily. With a negative step the selected
+    /// items must be yielded in reverse order, so they are buffered (and only them) on the first
+    /// call to `next`.
+    fn sliced(self, slice: &Slice) -> SlicedIterator<Self> {
+        SlicedIterator {
+            indices: slice.indices(self.len()),
+            inner: self,
+            pos: 0,
+            buffer: None,
+        }
+    }
+}
+
+impl<I> IteratorSliceExt for I where I: ExactSizeIterator {}
+
+/// An iterator adaptor that yields the items of an iterator selected by a slice.
+///
+/// This `struct` is created by the `sliced` method on `IteratorSliceExt`.
+pub struct SlicedIterator<I>
+where
+    I: Iterator,
+{
+    inner: I,
+    indices: Iter,
+    pos: usize,
+    buffer: Option<Vec<I::Item>>,
+}
+
+impl<I> Iterator for SlicedIterator<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.indices.forward {
+            let i = self.indices.next()?;
+            let item = self.inner.nth(i - self.pos);
+            self.pos = i + 1;
+            return item;
+        }
+
+        if self.buffer.is_none() {
+            let (indices, first) = (&self.indices, self.indices.clone().next());
+            let buffer = match first {
+                Some(first) => self
+                    .inner
+                    .by_ref()
+                    .take(first + 1)
+                    .enumerate()
+                    .filter(|&(i, _)| indices.contains(i))
+                    .map(|(_, x)| x)
+                    .collect(),
+                None => Vec::new(),
+            };
+            self.buffer = Some(buffer);
+        }
+        self.buffer.as_mut().and_then(Vec::pop)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = match &self.buffer {
+            Some(buffer) => buffer.len(),
+            None => self.indices.len(),
+        };
+        (n, Some(n))
+    }
+}
+
+impl<I> ExactSizeIterator for SlicedIterator<I> where I: Iterator {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Index;
+
+    #[test]
+    fn slyce() {
+        let v = [10, 20, 30, 40, 50];
+        assert_eq!(v.slyce("[::-2]"), Ok(vec![&50, &30, &10]));
+        assert!(v.slyce("[::-2").is_err());
+
+        let s = Slice {
+            start: Index::Tail(2),
+            end: Index::Default,
+            step: None,
+        };
+        assert_eq!([1, 2, 3].slyce(s), Ok(vec![&2, &3]));
+        assert_eq!(v.slyce(Slice::last_n(3)), Ok(vec![&30, &40, &50]));
+    }
+
+    #[test]
+    fn get_slice() {
+        let v = [10, 20, 30, 40, 50];
+        let get = |expr: &str| v.get_slice(&expr.parse().unwrap());
+        assert_eq!(get("[::]"), Some(vec![&10, &20, &30, &40, &50]));
+        assert_eq!(get("[1:5]"), Some(vec![&20, &30, &40, &50]));
+        assert_eq!(get("[-5:-4]"), Some(vec![&10]));
+        assert_eq!(get("[5:5]"), Some(vec![]));
+        assert_eq!(get("[4:-6:-2]"), Some(vec![&50, &30, &10]));
+        assert_eq!(get("[::0]"), Some(vec![]));
+        // the bounds that python would clamp.
+        assert_eq!(get("[1:6]"), None);
+        assert_eq!(get("[-6:]"), None);
+        assert_eq!(get("[5::-1]"), None);
+        assert_eq!(get("[:-7:-1]"), None);
+        assert_eq!(
+            [1, 2].get_slice(&"[::-1]".parse().unwrap()),
+            Some(vec![&2, &1])
+        );
+    }
+
+    #[test]
+    fn sliced() {
+        let sliced = |expr: &str| {
+            let s: Slice = expr.parse().unwrap();
+            (0..10).map(|i| i * 10).sliced(&s).collect::<Vec<_>>()
+        };
+
+        assert_eq!(sliced("[::]"), vec![0, 10, 20, 30, 40, 50, 60, 70, 80, 90]);
+        assert_eq!(sliced("[2:-3:3]"), vec![20, 50]);
+        assert_eq!(sliced("[-2::]"), vec![80, 90]);
+        assert_eq!(sliced("[::-4]"), vec![90, 50, 10]);
+        assert_eq!(sliced("[-3:2:-2]"), vec![70, 50, 30]);
+        assert_eq!(sliced("[2:5:-1]"), Vec::<i32>::new());
+        assert_eq!(sliced("[::0]"), Vec::<i32>::new());
+
+        for expr in &["[1::3]", "[::-3]", "[5:1]"] {
+            let s: Slice = expr.parse().unwrap();
+            let mut it = (0..10).sliced(&s);
+            let mut n = s.count(10);
+            assert_eq!(it.len(), n, "{}", expr);
+            while it.next().is_some() {
+                n -= 1;
+                assert_eq!(it.size_hint(), (n, Some(n)), "{}", expr);
+            }
+        }
+    }
+}
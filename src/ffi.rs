@@ -0,0 +1,101 @@
+//! A C ABI for parsing slice expressions and resolving them to indices.
+//!
+//! The matching header is `include/slyce.h`, which can be regenerated with
+//! `cbindgen --config cbindgen.toml --output include/slyce.h`.
+
+use crate::Slice;
+use std::boxed::Box;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::ptr;
+
+/// Parses a NUL-terminated slice expression such as `[1:-1:2]`.
+///
+/// Returns NULL if `expr` is NULL, not valid UTF-8 or not a valid slice expression. A non-NULL
+/// result must be released with `slyce_free`.
+///
+/// # Safety
+///
+/// `expr` must be NULL or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn slyce_parse(expr: *const c_char) -> *mut Slice {
+    if expr.is_null() {
+        return ptr::null_mut();
+    }
+    match CStr::from_ptr(expr)
+        .to_str()
+        .ok()
+        .and_then(|s| s.parse().ok())
+    {
+        Some(slice) => Box::into_raw(Box::new(slice)),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Writes to `out_buf` the indices selected by `slice` in a sequence of `len` elements, in
+/// order, stopping after `out_len` indices.
+///
+/// Returns the total number of selected indices, which can exceed `out_len`: calling it with a
+/// NULL `out_buf` and a zero `out_len` returns the size of the buffer to allocate.
+///
+/// # Safety
+///
+/// `slice` must have been returned by `slyce_parse` and not freed. `out_buf` must be valid for
+/// writing `out_len` elements, unless `out_len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn slyce_indices(
+    slice: *const Slice,
+    len: usize,
+    out_buf: *mut usize,
+    out_len: usize,
+) -> usize {
+    let slice = &*slice;
+    let mut indices = slice.indices(len);
+    let count = indices.len();
+    if out_len > 0 {
+        let out = std::slice::from_raw_parts_mut(out_buf, out_len);
+        for (o, i) in out.iter_mut().zip(&mut indices) {
+            *o = i;
+        }
+    }
+    count
+}
+
+/// Releases a slice returned by `slyce_parse`. Does nothing if `slice` is NULL.
+///
+/// # Safety
+///
+/// `slice` must be NULL or have been returned by `slyce_parse`, and not freed already.
+#[no_mangle]
+pub unsafe extern "C" fn slyce_free(slice: *mut Slice) {
+    if !slice.is_null() {
+        drop(Box::from_raw(slice));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ffi() {
+        unsafe {
+            let s = slyce_parse(b"[1:-1:2]\0".as_ptr() as *const c_char);
+            assert!(!s.is_null());
+            assert_eq!(slyce_indices(s, 8, ptr::null_mut(), 0), 3);
+
+            let mut buf = [0; 2];
+            assert_eq!(slyce_indices(s, 8, buf.as_mut_ptr(), buf.len()), 3);
+            assert_eq!(buf, [1, 3]);
+
+            let mut buf = [0; 4];
+            assert_eq!(slyce_indices(s, 8, buf.as_mut_ptr(), buf.len()), 3);
+            assert_eq!(buf, [1, 3, 5, 0]);
+            slyce_free(s);
+
+            assert!(slyce_parse(b"[1:2\0".as_ptr() as *const c_char).is_null());
+            assert!(slyce_parse(ptr::null()).is_null());
+            slyce_free(ptr::null_mut());
+        }
+    }
+}
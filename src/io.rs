@@ -0,0 +1,193 @@
+//! Helpers to read the selected parts of files and remote objects.
+
+use crate::Slice;
+use core::convert::TryFrom;
+use core::ops::Range;
+
+impl Slice {
+    /// Returns the byte ranges to read in order to get the bytes selected from an object of `len`
+    /// bytes, such as a file or an HTTP resource.
+    ///
+    /// Adjacent selected bytes are coalesced, so that the plan has as few ranges as possible:
+    /// a step of `1` or `-1` yields at most one range, any other step a range per byte. Ranges
+    /// are yielded in ascending order; with a negative step, the bytes must be reversed after
+    /// being read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` doesn't fit in a `usize`.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let s: Slice = "[-100::]".parse()?;
+    /// assert_eq!(s.read_plan(1000).collect::<Vec<_>>(), vec![900..1000]);
+    /// let s: Slice = "[::-4]".parse()?;
+    /// assert_eq!(s.read_plan(10).collect::<Vec<_>>(), vec![1..2, 5..6, 9..10]);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn read_plan(&self, len: u64) -> impl Iterator<Item = Range<u64>> {
+        let len = usize::try_from(len).expect("length fits in a usize");
+        let it = self.indices(len);
+        let n = it.len();
+        let lowest = match (n, it.forward) {
+            (0, _) => 0,
+            (_, true) => it.next,
+            (_, false) => it.next - (n - 1) * it.stride,
+        } as u64;
+        let (ranges, run, stride) = match it.stride {
+            1 => (n.min(1), n as u64, 0),
+            stride => (n, 1, stride as u64),
+        };
+        (0..ranges as u64).map(move |k| {
+            let start = lowest + k * stride;
+            start..start + run
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl Slice {
+    /// Returns the selected bytes of `buf` as a list of buffers for `Write::write_vectored`,
+    /// in selection order.
+    ///
+    /// Runs of adjacent bytes selected in ascending order share a buffer, so a step of `1`
+    /// yields at most one buffer; any other step yields a buffer per byte.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// use std::io::Write;
+    /// let buf: Vec<u8> = (0..=255).collect();
+    /// let mut bufs = "[100:200]".parse::<Slice>()?.io_slices(&buf);
+    /// bufs.extend("[-64:]".parse::<Slice>()?.io_slices(&buf));
+    /// assert_eq!(bufs.len(), 2);
+    ///
+    /// let mut out: Vec<u8> = Vec::new();
+    /// out.write_vectored(&bufs)?;
+    /// assert_eq!(out.len(), 164);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn io_slices<'a>(&self, buf: &'a [u8]) -> Vec<std::io::IoSlice<'a>> {
+        let mut runs: Vec<Range<usize>> = Vec::new();
+        for i in self.indices(buf.len()) {
+            match runs.last_mut() {
+                Some(run) if run.end == i => run.end += 1,
+                _ => runs.push(i..i + 1),
+            }
+        }
+        runs.into_iter()
+            .map(|run| std::io::IoSlice::new(&buf[run]))
+            .collect()
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Slice {
+    /// Reads the bytes of `reader` selected by the slice, seeking past the bytes that aren't.
+    ///
+    /// The length of `reader` is found by seeking to its end, and the bytes are then read
+    /// following `read_plan`. The position of `reader` is left unspecified.
+    ///
+    /// An error of kind `InvalidInput` is returned if the length of `reader` doesn't fit in a
+    /// `usize`.
+    pub async fn read_async<R>(&self, reader: &mut R) -> std::io::Result<alloc::vec::Vec<u8>>
+    where
+        R: tokio::io::AsyncRead + tokio::io::AsyncSeek + Unpin,
+    {
+        use std::io::SeekFrom;
+        use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+        let len = reader.seek(SeekFrom::End(0)).await?;
+        let len = usize::try_from(len).map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "the reader is too long to be sliced on this target",
+            )
+        })?;
+        let mut r = alloc::vec::Vec::with_capacity(self.count(len));
+        for range in self.read_plan(len as u64) {
+            let n = r.len();
+            r.resize(n + (range.end - range.start) as usize, 0);
+            reader.seek(SeekFrom::Start(range.start)).await?;
+            reader.read_exact(&mut r[n..]).await?;
+        }
+        if self.step_or_default() < 0 {
+            r.reverse();
+        }
+        Ok(r)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn read_plan() {
+        let plan = |expr: &str, len| {
+            let s: Slice = expr.parse().unwrap();
+            s.read_plan(len).collect::<Vec<_>>()
+        };
+        assert_eq!(plan("[::]", 10), [0..10]);
+        assert_eq!(plan("[2:-2:]", 10), [2..8]);
+        assert_eq!(plan("[::-1]", 10), [0..10]);
+        assert_eq!(plan("[1::3]", 10), [1..2, 4..5, 7..8]);
+        assert_eq!(plan("[3:4:5]", 10), [3..4]);
+        assert_eq!(plan("[-2:-9:-3]", 10), [2..3, 5..6, 8..9]);
+        assert_eq!(plan("[8:1:-1]", 10), [2..9]);
+        assert_eq!(plan("[-20:20:]", 10), [0..10]);
+        assert_eq!(plan("[5:5:]", 10), []);
+        assert_eq!(plan("[::0]", 10), []);
+        assert_eq!(plan("[::]", 0), []);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn io_slices() {
+        let data: Vec<u8> = (0..20).collect();
+        for expr in &[
+            "[::]", "[3:8:]", "[::-1]", "[1::3]", "[-4::]", "[5:5:]", "[::0]",
+        ] {
+            let s: Slice = expr.parse().unwrap();
+            let bufs = s.io_slices(&data);
+            let got: Vec<u8> = bufs.iter().flat_map(|b| b.iter().copied()).collect();
+            let want: Vec<u8> = s.apply(&data).copied().collect();
+            assert_eq!(got, want, "{}", expr);
+            let step = s.step.unwrap_or(1);
+            assert_eq!(
+                bufs.len(),
+                if step == 1 {
+                    want.len().min(1)
+                } else {
+                    want.len()
+                }
+            );
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn read_async() {
+        // in-memory readers never wait, so no tokio runtime is needed.
+        let data: Vec<u8> = (0..100).collect();
+        let read = |expr: &str| {
+            let s: Slice = expr.parse().unwrap();
+            let mut reader = std::io::Cursor::new(&data);
+            futures::executor::block_on(s.read_async(&mut reader)).unwrap()
+        };
+        for expr in &[
+            "[::]",
+            "[-10::]",
+            "[5:50:7]",
+            "[::-3]",
+            "[-1:-5:-1]",
+            "[5:5:]",
+        ] {
+            let s: Slice = expr.parse().unwrap();
+            let want: Vec<u8> = s.apply(&data).copied().collect();
+            assert_eq!(read(expr), want, "{}", expr);
+        }
+    }
+}
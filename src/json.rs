@@ -0,0 +1,53 @@
+//! Slicing of `serde_json` arrays.
+
+use crate::Slice;
+use alloc::vec::Vec;
+use serde_json::Value;
+
+impl Slice {
+    /// Returns references to the elements of a JSON array that match the slice expression, or
+    /// `None` if `v` is not an array.
+    pub fn apply_json<'a>(&self, v: &'a Value) -> Option<Vec<&'a Value>> {
+        v.as_array().map(|a| self.apply_to_vec(a))
+    }
+
+    /// Keeps only the elements of a JSON array that match the slice expression, in selection
+    /// order.
+    ///
+    /// Returns `false`, leaving `v` untouched, if it is not an array.
+    pub fn slice_json_in_place(&self, v: &mut Value) -> bool {
+        match v.as_array_mut() {
+            Some(a) => {
+                self.retain_selected(a);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn apply_json() {
+        let s: Slice = "[1::2]".parse().unwrap();
+        let v = json!([0, "a", 2, {"b": 3}]);
+        assert_eq!(s.apply_json(&v), Some(vec![&json!("a"), &json!({"b": 3})]));
+        assert_eq!(s.apply_json(&json!({"a": 1})), None);
+    }
+
+    #[test]
+    fn slice_json_in_place() {
+        let s: Slice = "[::-2]".parse().unwrap();
+        let mut v = json!({"items": [1, 2, 3, 4, 5]});
+        assert!(s.slice_json_in_place(&mut v["items"]));
+        assert_eq!(v, json!({"items": [5, 3, 1]}));
+
+        let mut v = json!("abc");
+        assert!(!s.slice_json_in_place(&mut v));
+        assert_eq!(v, json!("abc"));
+    }
+}
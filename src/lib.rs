@@ -83,15 +83,123 @@
 //! let s = slyce::Slice{start: Index::Tail(1000), end: 2000.into(), step: None};
 //! assert_eq!(render(s), "[10, 20, 30, 40, 50]");
 //! ```
-
-use std::borrow::Borrow;
-use std::default::Default;
-use std::fmt;
-use std::ops::RangeInclusive;
+//!
+//! # no_std
+//!
+//! The core slicing algorithm doesn't need the standard library. Disable the default `std`
+//! feature to use this crate in `#![no_std]` environments; the `alloc` feature brings back the
+//! APIs that need to allocate (e.g. the ones operating on `Vec`).
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "tokio")]
+extern crate dep_tokio as tokio;
+
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::default::Default;
+use core::fmt;
+use core::ops::{Add, Bound, Range, RangeBounds, RangeInclusive, Sub};
+
+#[cfg(feature = "arbitrary")]
+mod arb;
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(all(feature = "bitvec", feature = "alloc"))]
+mod bits;
+#[cfg(all(feature = "bytes", feature = "alloc"))]
+mod bytes;
+mod combinators;
+mod const_slice;
+#[cfg(feature = "alloc")]
+mod deque;
+mod error;
+#[cfg(feature = "alloc")]
+mod ext;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod io;
+#[cfg(all(feature = "serde_json", feature = "alloc"))]
+mod json;
+#[cfg(feature = "indexmap")]
+mod map;
+#[cfg(feature = "alloc")]
+mod multi;
+#[cfg(feature = "ndarray")]
+mod nd;
+#[cfg(feature = "nom")]
+pub mod nom;
+#[cfg(all(feature = "alloc", any(test, feature = "oracle")))]
+pub mod oracle;
+#[cfg(feature = "rayon")]
+mod par;
+mod parse;
+#[cfg(feature = "im")]
+mod persistent;
+#[cfg(feature = "pyo3")]
+mod py;
+#[cfg(all(feature = "quickcheck", feature = "alloc"))]
+mod qc;
+mod range;
+#[cfg(all(feature = "csv", feature = "std"))]
+mod record;
+pub mod rfc9535;
+#[cfg(feature = "alloc")]
+mod selector;
+#[cfg(feature = "alloc")]
+mod shared;
+#[cfg(all(feature = "simd", feature = "alloc"))]
+mod simd;
+mod sliceable;
+#[cfg(feature = "alloc")]
+mod slyced;
+#[cfg(feature = "alloc")]
+mod sql;
+#[cfg(feature = "proptest")]
+mod strategy;
+#[cfg(feature = "alloc")]
+mod stream;
+mod text;
+#[cfg(feature = "alloc")]
+mod vec;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use const_slice::{ConstSlice, DEFAULT};
+pub use error::{EmptyReason, Error};
+#[cfg(feature = "alloc")]
+pub use ext::{IteratorSliceExt, SliceExt, SlicedIterator};
+#[cfg(feature = "alloc")]
+pub use multi::{AxisSelector, MultiSlice};
+pub use parse::{ParseError, ParseErrorKind, Token};
+#[cfg(feature = "alloc")]
+pub use selector::Selector;
+#[cfg(feature = "alloc")]
+pub use shared::SharedSlice;
+pub use sliceable::Sliceable;
+#[cfg(feature = "macros")]
+pub use slyce_macros::slyce;
+#[cfg(feature = "alloc")]
+pub use slyced::{SlyceVec, Slyced};
+#[cfg(feature = "alloc")]
+pub use sql::SqlDialect;
+#[cfg(feature = "std")]
+pub use stream::SlicedLines;
+#[cfg(all(feature = "csv", feature = "std"))]
+pub use stream::SlicedRecords;
+#[cfg(feature = "alloc")]
+pub use stream::StreamIter;
+pub use text::SlicedChars;
+#[cfg(feature = "unicode-segmentation")]
+pub use text::SlicedGraphemes;
+#[cfg(feature = "alloc")]
+pub use text::StrIndex;
 
 /// A slice has an optional start, an optional end, and an optional step.
 #[derive(Debug, Clone)]
-#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Slice {
     pub start: Index,
     pub end: Index,
@@ -99,13 +207,23 @@ pub struct Slice {
 }
 
 impl fmt::Display for Slice {
+    /// Formats the slice as a `[start:end:step]` expression, which `from_str` parses back into
+    /// an equivalent slice, i.e. one that selects the same elements of any array.
+    ///
+    /// Omitted indices and steps are written as empty parts. `Tail(0)` has no spelling of its
+    /// own, since `-0` is the same as `0`: it's written as `usize::MAX`, which denotes the same
+    /// position past the end of any array.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let spell = |i: &Index| match *i {
+            Tail(0) => Head(usize::MAX),
+            ref i => i.clone(),
+        };
         write!(
             f,
             "[{}:{}:{}]",
-            self.start,
-            self.end,
-            self.step.map_or("".to_string(), |n| n.to_string())
+            spell(&self.start),
+            spell(&self.end),
+            Step::from(self.step)
         )
     }
 }
@@ -115,7 +233,6 @@ impl fmt::Display for Slice {
 /// Tail indices are represented with a distinct enumeration variant so that the full index
 /// numeric range (usize) can be utilized without numeric overflows.
 #[derive(Debug, Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Index {
     /// Position in the array relative to the start of the array (i.e. absolute position).
     /// Head(0) is the position of the first element of a non-empty array.
@@ -139,86 +256,967 @@ impl fmt::Display for Index {
     }
 }
 
+/// The step of a slice, spelling out the omitted step like `Index::Default` does for omitted
+/// indices.
+///
+/// `Slice` stores its step as an `Option<isize>`, where `None` is the omitted step; `Step`
+/// converts from and into it, and displays and parses the step as it appears in a slice
+/// expression.
+///
+/// # Example
+/// ```
+/// use slyce::{Slice, Step};
+/// let s: Slice = "[1::]".parse()?;
+/// assert_eq!(Step::from(s.step), Step::Default);
+/// assert_eq!(Step::from(s.step).get(), 1);
+/// assert_eq!(Step::Value(-2).to_string(), "-2");
+/// assert_eq!("".parse::<Step>()?, Step::Default);
+/// # Ok::<(), slyce::ParseError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// The step was omitted; it selects every element going forward, like a step of 1.
+    Default,
+    /// An explicit step.
+    Value(isize),
+}
+
+impl Step {
+    /// Returns the step to iterate with: the value of an explicit step, or 1.
+    pub const fn get(self) -> isize {
+        match self {
+            Step::Default => 1,
+            Step::Value(n) => n,
+        }
+    }
+
+    /// Converts the step of a `Slice`. This is the same as `From<Option<isize>>`, but it can be
+    /// used in constant expressions.
+    pub const fn from_option(step: Option<isize>) -> Self {
+        match step {
+            None => Step::Default,
+            Some(n) => Step::Value(n),
+        }
+    }
+
+    /// Converts the step back into the representation used by `Slice`.
+    pub const fn to_option(self) -> Option<isize> {
+        match self {
+            Step::Default => None,
+            Step::Value(n) => Some(n),
+        }
+    }
+}
+
+#[allow(clippy::derivable_impls)]
+impl core::default::Default for Step {
+    fn default() -> Self {
+        Step::Default
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Default => Ok(()),
+            Step::Value(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+impl From<Option<isize>> for Step {
+    fn from(step: Option<isize>) -> Self {
+        Step::from_option(step)
+    }
+}
+
+impl From<isize> for Step {
+    fn from(n: isize) -> Self {
+        Step::Value(n)
+    }
+}
+
+impl From<Step> for Option<isize> {
+    fn from(step: Step) -> Self {
+        step.to_option()
+    }
+}
+
 impl Index {
-    /// to_bound transforms an index slice parameter into an array bound.
-    /// An array bound can be -1 in order to represent the exclusive lower
-    /// bound 0.
+    /// Converts an integer into an index relative to the start of the array if it's
+    /// non-negative, or relative to the end of the array otherwise. This is the same as
+    /// `From<isize>`, but it can be used in constant expressions.
+    pub const fn from_isize(i: isize) -> Self {
+        // wrapping_abs maps isize::MIN to itself, which is the right magnitude as a usize.
+        let n = i.wrapping_abs() as usize;
+        if i < 0 {
+            Tail(n)
+        } else {
+            Head(n)
+        }
+    }
+
+    /// Returns the position of the element the index denotes in an array of length `len`, or
+    /// `None` if it's out of bounds. `Default` denotes no element in particular, so it always
+    /// returns `None`.
     ///
-    /// This impl won't work if size_of<usize> >= 16.
-    /// 64 bits of address space should be enough for everybody.
-    fn to_bound(&self, len: i128, r: &RangeInclusive<i128>) -> Option<i128> {
-        match self {
-            &Head(n) => Some(n as i128),
-            &Tail(n) => Some(len - (n as i128)),
-            Default => None,
+    /// # Example
+    /// ```
+    /// use slyce::Index;
+    /// assert_eq!(Index::from(-2).resolve(5), Some(3));
+    /// assert_eq!(Index::from(5).resolve(5), None);
+    /// ```
+    pub const fn resolve(&self, len: usize) -> Option<usize> {
+        match *self {
+            Head(n) if n < len => Some(n),
+            Tail(n) if n >= 1 && n <= len => Some(len - n),
+            _ => None,
+        }
+    }
+
+    /// Compares the positions the two indices denote in an array of length `len`.
+    ///
+    /// Positions outside of the array are compared as they are, without clamping them to the
+    /// array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is `Default`, which denotes no position in particular: whether
+    /// it stands for the start or the end of the array depends on its role in a slice.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Index;
+    /// use std::cmp::Ordering;
+    /// assert_eq!(Index::Head(1).cmp_with_len(&Index::Tail(2), 5), Ordering::Less);
+    /// assert_eq!(Index::Head(3).cmp_with_len(&Index::Tail(2), 5), Ordering::Equal);
+    /// assert_eq!(Index::Head(3).cmp_with_len(&Index::Tail(2), 4), Ordering::Greater);
+    /// ```
+    pub fn cmp_with_len(&self, other: &Index, len: usize) -> Ordering {
+        match (self, other) {
+            (&Head(a), &Head(b)) => a.cmp(&b),
+            (&Tail(a), &Tail(b)) => b.cmp(&a),
+            // `len - b` can be negative, so compare `a + b` with `len` instead.
+            (&Head(a), &Tail(b)) => match a.checked_add(b) {
+                Some(sum) => sum.cmp(&len),
+                None => Ordering::Greater,
+            },
+            (&Tail(_), &Head(_)) => other.cmp_with_len(self, len).reverse(),
+            (Default, _) | (_, Default) => panic!("Index::Default can't be compared"),
+        }
+    }
+
+    /// Returns the index `k` positions further from the start of the array, or closer to it if
+    /// `k` is negative. This is the same as adding or subtracting `k.unsigned_abs()`.
+    pub fn offset_by(self, k: isize) -> Self {
+        if k < 0 {
+            self - k.unsigned_abs()
+        } else {
+            self + k as usize
+        }
+    }
+
+    /// Transforms the index, used as a bound of a slice iterating `forward` or backward over an
+    /// array of length `len`, into an array bound; `Default` has no bound of its own and yields
+    /// `None`.
+    ///
+    /// Bounds are "cuts" between array elements, in the range `0..=len`: a forward bound `b`
+    /// denotes the position of the element at index `b`, while a backward bound `b` denotes
+    /// the position of the element at index `b - 1`. This way the exclusive backward lower
+    /// bound "-1" is represented as 0 and all the arithmetic stays within `usize`.
+    ///
+    /// Indices outside of the array are clamped to it, following python. Containers that
+    /// implement their own slicing can use this, or `Slice::bounds`, to get the same results as
+    /// `Slice::apply`.
+    pub const fn to_bound(&self, len: usize, forward: bool) -> Option<usize> {
+        match (self, forward) {
+            (&Head(n), true) => Some(clamp(n, 0..=len)),
+            (&Head(n), false) => Some(clamp(n.saturating_add(1), 0..=len)),
+            (&Tail(n), true) => Some(len.saturating_sub(n)),
+            (&Tail(n), false) => Some(len.saturating_sub(n.saturating_sub(1))),
+            (Default, _) => None,
         }
-        .map(|n| clamp(n, r))
     }
 }
 
-fn clamp<T, R>(n: T, r: R) -> T
-where
-    T: Ord + Copy,
-    R: Borrow<RangeInclusive<T>>,
-{
-    let r = r.borrow();
+/// Returns a reference to the element at index `i`, which must come from `Slice::indices`
+/// called with the length of `arr`.
+#[cfg(not(feature = "unchecked"))]
+#[inline(always)]
+fn get<T>(arr: &[T], i: usize) -> &T {
+    &arr[i]
+}
+
+/// Returns a reference to the element at index `i`, which must come from `Slice::indices`
+/// called with the length of `arr`.
+#[cfg(feature = "unchecked")]
+#[inline(always)]
+fn get<T>(arr: &[T], i: usize) -> &T {
+    // SAFETY: `indices(len)` clamps both bounds to the array and only yields values strictly
+    // between them, so every index it yields is lower than `len`.
+    unsafe { arr.get_unchecked(i) }
+}
+
+const fn clamp(n: usize, r: RangeInclusive<usize>) -> usize {
     let (start, end) = (*r.start(), *r.end());
-    n.max(start).min(end)
+    if n < start {
+        start
+    } else if n > end {
+        end
+    } else {
+        n
+    }
 }
 
 impl Slice {
-    /// Returns an iterator that yields the elements that match the slice expression.
-    pub fn apply<'a, T>(&self, arr: &'a [T]) -> impl Iterator<Item = &'a T> + 'a {
-        self.indices(arr.len()).map(move |i| &arr[i])
+    /// Creates a new slice. Unlike a struct literal, this can be used in constant expressions
+    /// together with `Index::from_isize`.
+    pub const fn new(start: Index, end: Index, step: Option<isize>) -> Self {
+        Slice { start, end, step }
     }
 
-    /// Returns an iterator that yields the indices that match the slice expression.
-    fn indices(&self, ulen: usize) -> impl Iterator<Item = usize> {
-        let len = ulen as i128;
-        let step = self.step.unwrap_or(1);
+    /// Returns the number of elements the slice selects in an array of length `len`.
+    ///
+    /// This is computed in constant time and can be evaluated at compile time.
+    pub const fn count(&self, len: usize) -> usize {
+        self.indices(len).remaining
+    }
+
+    /// Returns the index of the `n`-th element the slice selects in an array of length `len`,
+    /// or `None` if the slice selects `n` elements or less.
+    ///
+    /// This is computed in constant time and can be evaluated at compile time.
+    pub const fn nth(&self, len: usize, n: usize) -> Option<usize> {
+        let it = self.indices(len);
+        if n >= it.remaining {
+            return None;
+        }
+        // n < remaining, so the offset stays within the array and the wrapping arithmetic
+        // yields the same result as moving by n strides in the direction of the iteration.
+        Some(it.next.wrapping_add(n.wrapping_mul(it.delta)))
+    }
+
+    /// Returns the index of the first element the slice selects in an array of length `len`, in
+    /// selection order, or `None` if it selects nothing.
+    ///
+    /// This is computed in constant time and can be evaluated at compile time.
+    pub const fn first_index(&self, len: usize) -> Option<usize> {
+        self.nth(len, 0)
+    }
+
+    /// Returns the index of the last element the slice selects in an array of length `len`, in
+    /// selection order, or `None` if it selects nothing.
+    ///
+    /// This is computed in constant time and can be evaluated at compile time.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let s: Slice = "[8:1:-3]".parse()?;
+    /// assert_eq!((s.first_index(10), s.last_index(10)), (Some(8), Some(2)));
+    /// assert_eq!((s.first_index(0), s.last_index(0)), (None, None));
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub const fn last_index(&self, len: usize) -> Option<usize> {
+        match self.count(len) {
+            0 => None,
+            n => self.nth(len, n - 1),
+        }
+    }
 
-        let (def_start, def_end) = if step >= 0 { (0, len) } else { (len - 1, -1) };
+    /// Describes the selection in an array of length `len` as a strided view: the position of
+    /// the first selected element, the signed distance between consecutive ones and their
+    /// number. Returns `None` if the slice selects nothing.
+    ///
+    /// This is the representation used by BLAS, GPU buffer APIs and numpy-like libraries, so a
+    /// selection can be handed to them without copying it.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::{Slice, ViewDesc};
+    /// let s: Slice = "[-1:0:-3]".parse()?;
+    /// let desc = s.view_descriptor(10).unwrap();
+    /// assert_eq!(desc, ViewDesc { offset: 9, stride: -3, count: 3 });
+    /// assert_eq!(s.view_descriptor(0), None);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub const fn view_descriptor(&self, len: usize) -> Option<ViewDesc> {
+        let it = self.indices(len);
+        if it.remaining == 0 {
+            return None;
+        }
+        Some(ViewDesc {
+            offset: it.next,
+            stride: self.step_or_default(),
+            count: it.remaining,
+        })
+    }
 
-        let bounds = if step >= 0 {
-            def_start..=def_end
+    /// Returns a string with a character per position of an array of length `len`: `#` if the
+    /// slice selects it and `.` otherwise. The selection order isn't shown.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let s: Slice = "[-2:1:-3]".parse()?;
+    /// assert_eq!(s.mask_string(8), "...#..#.");
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn mask_string(&self, len: usize) -> alloc::string::String {
+        let it = self.indices(len);
+        (0..len)
+            .map(|i| if it.contains(i) { '#' } else { '.' })
+            .collect()
+    }
+
+    /// Returns an iterator that yields the elements that match the slice expression.
+    ///
+    /// The input can be anything that can be viewed as a slice, such as arrays, `Vec`s,
+    /// boxed slices or `Arc<[T]>`.
+    pub fn apply<'a, T, A>(&self, arr: &'a A) -> impl Iterator<Item = &'a T> + 'a
+    where
+        A: AsRef<[T]> + ?Sized,
+        T: 'a,
+    {
+        Elements::new(arr.as_ref(), self.resolve_traced(arr.as_ref().len()))
+    }
+
+    /// Returns an iterator that yields the elements that match the slice expression, or the
+    /// reason why there are none.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::{EmptyReason, Slice};
+    /// let v = [1, 2, 3, 4, 5];
+    /// let s: Slice = "[1:3]".parse()?;
+    /// assert_eq!(s.try_apply(&v).unwrap().collect::<Vec<_>>(), vec![&2, &3]);
+    /// let s: Slice = "[3:1]".parse()?;
+    /// assert_eq!(s.try_apply(&v).err(), Some(EmptyReason::EndBeforeStart));
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn try_apply<'a, T>(
+        &self,
+        arr: &'a [T],
+    ) -> Result<impl Iterator<Item = &'a T> + 'a, EmptyReason> {
+        match self.empty_reason(arr.len()) {
+            Some(reason) => Err(reason),
+            None => Ok(self.apply(arr)),
+        }
+    }
+
+    /// Returns why the slice selects no element of an array of length `len`, or `None` if it
+    /// selects some. When there are several reasons, the first one in the order of the
+    /// `EmptyReason` variants is returned.
+    pub const fn empty_reason(&self, len: usize) -> Option<EmptyReason> {
+        let (start, end, forward) = self.bounds(len);
+        if self.step_or_default() == 0 {
+            Some(EmptyReason::ZeroStep)
+        } else if len == 0 {
+            Some(EmptyReason::EmptyInput)
+        } else if (forward && start == len) || (!forward && start == 0) {
+            Some(EmptyReason::StartOutOfBounds)
+        } else if (forward && end <= start) || (!forward && end >= start) {
+            Some(EmptyReason::EndBeforeStart)
         } else {
-            def_end..=def_start
+            None
+        }
+    }
+
+    /// Returns an iterator that yields the elements that match the slice expression, each paired
+    /// with its position in the original array.
+    pub fn apply_enumerated<'a, T>(
+        &self,
+        arr: &'a [T],
+    ) -> impl Iterator<Item = (usize, &'a T)> + 'a {
+        self.resolve_traced(arr.len()).map(move |i| (i, &arr[i]))
+    }
+
+    /// Returns an iterator that yields clones of the elements that match the slice expression.
+    pub fn apply_cloned<'a, T>(&self, arr: &'a [T]) -> impl Iterator<Item = T> + 'a
+    where
+        T: Clone,
+    {
+        self.resolve_traced(arr.len()).map(move |i| arr[i].clone())
+    }
+
+    /// Returns an iterator that yields copies of the elements that match the slice expression.
+    pub fn apply_copied<'a, T>(&self, arr: &'a [T]) -> impl Iterator<Item = T> + 'a
+    where
+        T: Copy,
+    {
+        self.resolve_traced(arr.len()).map(move |i| arr[i])
+    }
+
+    /// Returns an iterator that calls `f` with each position selected in a sequence of length
+    /// `len`, in selection order, and yields its results.
+    ///
+    /// This is useful to slice data that can only be accessed through a function, such as remote
+    /// or paged storage. `f` is called lazily, only for the selected positions.
+    pub fn apply_fn<T, F>(&self, len: usize, f: F) -> impl Iterator<Item = T>
+    where
+        F: FnMut(usize) -> T,
+    {
+        self.resolve_traced(len).map(f)
+    }
+
+    /// Returns an iterator that yields mutable references to the elements that match the slice
+    /// expression.
+    pub fn apply_mut<'a, T>(&self, arr: &'a mut [T]) -> impl Iterator<Item = &'a mut T> + 'a {
+        let len = arr.len();
+        let ptr = arr.as_mut_ptr();
+        // SAFETY: `indices` yields strictly increasing or strictly decreasing values that are
+        // all lower than `len`, so each element is borrowed at most once and never outlives `arr`.
+        self.resolve_traced(len)
+            .map(move |i| unsafe { &mut *ptr.add(i) })
+    }
+
+    /// Overwrites the elements that match the slice expression with clones of `value`, like
+    /// python's `a[s] = x` with a scalar `x`.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let mut samples = [3, 1, 4, 1, 5, 9, 2, 6];
+    /// let s: Slice = "[::3]".parse()?;
+    /// s.fill(&mut samples, 0);
+    /// assert_eq!(samples, [0, 1, 4, 0, 5, 9, 0, 6]);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn fill<T>(&self, arr: &mut [T], value: T)
+    where
+        T: Clone,
+    {
+        for i in self.resolve_traced(arr.len()) {
+            arr[i] = value.clone();
+        }
+    }
+
+    /// Calls `f` on each element that matches the slice expression, in selection order.
+    ///
+    /// This does what iterating over `apply_mut` does, for callers that find a callback easier
+    /// to provide than an iterator to drive.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let mut v = [1, 2, 3, 4, 5];
+    /// let s: Slice = "[::-2]".parse()?;
+    /// let mut order = vec![];
+    /// s.map_in_place(&mut v, |x| {
+    ///     order.push(*x);
+    ///     *x *= 10;
+    /// });
+    /// assert_eq!(v, [10, 2, 30, 4, 50]);
+    /// assert_eq!(order, vec![5, 3, 1]);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn map_in_place<T, F>(&self, arr: &mut [T], mut f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        for i in self.resolve_traced(arr.len()) {
+            f(&mut arr[i]);
+        }
+    }
+
+    /// Swaps the elements selected by this slice with the elements selected by `other`, pairwise
+    /// in selection order.
+    ///
+    /// Both slices must select the same number of elements and must not select any element in
+    /// common; otherwise an error is returned and `arr` is left untouched.
+    pub fn swap_slices<T>(&self, other: &Slice, arr: &mut [T]) -> Result<(), Error> {
+        let (a, b) = (self.indices(arr.len()), other.indices(arr.len()));
+        let (expected, actual) = (a.clone().count(), b.clone().count());
+        if expected != actual {
+            return Err(Error::LengthMismatch { expected, actual });
+        }
+        if let Some(index) = b.clone().filter(|&i| a.contains(i)).min() {
+            return Err(Error::Overlap { index });
+        }
+        for (i, j) in a.zip(b) {
+            arr.swap(i, j);
+        }
+        Ok(())
+    }
+
+    /// Returns the start and end bounds of the slice for an array of length `len`, along with
+    /// the direction of the iteration (`true` for forward). See `Index::to_bound` for the
+    /// meaning of bounds.
+    ///
+    /// The slice selects every `step.unsigned_abs()`-th element from the start bound, included,
+    /// up to the end bound, excluded; nothing is selected if the end bound isn't past the start
+    /// bound in the direction of the iteration, or if the step is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let s: Slice = "[-2::-1]".parse()?;
+    /// // iterating backward from the cut before index 4 to the one before index 0 visits
+    /// // 3, 2, 1 and 0.
+    /// assert_eq!(s.bounds(5), (4, 0, false));
+    /// assert_eq!(s.apply_fn(5, |i| i).collect::<Vec<_>>(), vec![3, 2, 1, 0]);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub const fn bounds(&self, len: usize) -> (usize, usize, bool) {
+        let forward = self.step_or_default() >= 0;
+        let (def_start, def_end) = if forward { (0, len) } else { (len, 0) };
+        let start = match self.start.to_bound(len, forward) {
+            Some(b) => b,
+            None => def_start,
+        };
+        let end = match self.end.to_bound(len, forward) {
+            Some(b) => b,
+            None => def_end,
         };
+        (start, end, forward)
+    }
+
+    /// Resolves the slice against an array of length `len`, replacing its indices with the
+    /// bounds returned by `bounds` and its step with the one to iterate with.
+    ///
+    /// When the step is 1 or -1, `as_contiguous` returns a `RangeBounds<usize>` that can be
+    /// passed to the std APIs that take a range, such as `Vec::drain`.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let mut v = vec![1, 2, 3, 4, 5];
+    /// let s: Slice = "[1:-1]".parse()?;
+    /// let drained: Vec<_> = v.drain(s.normalize(v.len()).as_contiguous().unwrap()).collect();
+    /// assert_eq!(drained, vec![2, 3, 4]);
+    /// assert_eq!(v, vec![1, 5]);
+    ///
+    /// let s: Slice = "[::2]".parse()?;
+    /// assert_eq!(s.normalize(v.len()).as_contiguous(), None);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub const fn normalize(&self, len: usize) -> NormalizedSlice {
+        let (start, end, _) = self.bounds(len);
+        NormalizedSlice {
+            start,
+            end,
+            step: self.step_or_default(),
+        }
+    }
 
+    /// Resolves the slice against arrays of length `len` once, so that it can be applied
+    /// cheaply to many arrays of that length.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let s: Slice = "[-3::-1]".parse()?;
+    /// let prepared = s.prepare(5);
+    /// for row in &[[1, 2, 3, 4, 5], [6, 7, 8, 9, 10]] {
+    ///     assert_eq!(prepared.apply(row).count(), 3);
+    /// }
+    /// assert!(prepared.contains(0) && !prepared.contains(4));
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub const fn prepare(&self, len: usize) -> PreparedSlice {
+        PreparedSlice {
+            len,
+            indices: self.indices(len),
+        }
+    }
+
+    /// Pairs the slice with the length of an array, giving a value that can be iterated to get
+    /// the selected positions, in selection order.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let s: Slice = "[::-2]".parse()?;
+    /// let mut positions = vec![];
+    /// for i in s.over(5) {
+    ///     positions.push(i);
+    /// }
+    /// assert_eq!(positions, vec![4, 2, 0]);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub const fn over(&self, len: usize) -> Over<'_> {
+        Over { slice: self, len }
+    }
+
+    const fn step_or_default(&self) -> isize {
+        Step::from_option(self.step).get()
+    }
+
+    /// Like `indices`, but with the `tracing` feature it also emits a debug event describing the
+    /// resolved selection, and why it's empty if it is.
+    #[inline]
+    fn resolve_traced(&self, len: usize) -> Iter {
+        let it = self.indices(len);
+        #[cfg(feature = "tracing")]
+        {
+            let (start, end, _) = self.bounds(len);
+            tracing::debug!(
+                slice = %self,
+                len,
+                start,
+                end,
+                step = self.step_or_default(),
+                count = it.len(),
+                empty = ?self.empty_reason(len),
+                "resolved slice"
+            );
+        }
+        it
+    }
+
+    /// Returns an iterator that yields the indices that match the slice expression.
+    const fn indices(&self, len: usize) -> Iter {
+        let (start, end, forward) = self.bounds(len);
+        // wrapping_abs maps isize::MIN to itself, which is the right magnitude as a usize.
+        let stride = self.step_or_default().wrapping_abs() as usize;
+
+        let remaining = match (stride, forward) {
+            (0, _) => 0,
+            (_, true) if start < end => (end - start - 1) / stride + 1,
+            (_, false) if start > end => (start - end - 1) / stride + 1,
+            _ => 0,
+        };
         Iter {
-            i: self.start.to_bound(len, &bounds).unwrap_or(def_start),
-            end: self.end.to_bound(len, &bounds).unwrap_or(def_end),
-            step: step as i128,
+            next: if forward {
+                start
+            } else {
+                start.saturating_sub(1)
+            },
+            remaining,
+            stride,
+            forward,
+            delta: if forward {
+                stride
+            } else {
+                stride.wrapping_neg()
+            },
+        }
+    }
+}
+
+/// A selection described as a strided view of an array.
+///
+/// This `struct` is created by the `view_descriptor` method on `Slice`. The selected positions
+/// are `offset + k * stride` for `k` in `0..count`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewDesc {
+    /// Position of the first selected element.
+    pub offset: usize,
+    /// Signed distance between two consecutive selected elements.
+    pub stride: isize,
+    /// Number of selected elements; always at least one.
+    pub count: usize,
+}
+
+/// A slice resolved against an array of a given length.
+///
+/// This `struct` is created by the `normalize` method on `Slice`. `start` and `end` are bounds
+/// as described in `Index::to_bound`, always within the array, and `step` is never omitted.
+///
+/// Use `as_contiguous` to pass the selection to std APIs that take a range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NormalizedSlice {
+    /// The bound the iteration starts from, included.
+    pub start: usize,
+    /// The bound the iteration ends at, excluded.
+    pub end: usize,
+    /// The step of the iteration.
+    pub step: isize,
+}
+
+impl NormalizedSlice {
+    /// Returns true if the slice selects all the positions between its bounds, i.e. if its step
+    /// is 1 or -1.
+    pub const fn is_contiguous(&self) -> bool {
+        self.step == 1 || self.step == -1
+    }
+
+    /// Returns the positions between the bounds, which is empty when the end bound isn't past
+    /// the start bound in the direction of the iteration.
+    pub const fn range(&self) -> Range<usize> {
+        match (self.step >= 0, self.start < self.end) {
+            (true, true) => self.start..self.end,
+            (false, false) => self.end..self.start,
+            _ => self.start..self.start,
         }
     }
+
+    /// Returns the selected positions if the slice selects all the positions between its
+    /// bounds, i.e. if its step is 1 or -1, in which case they are selected in reverse order.
+    /// Returns `None` for any other step.
+    pub const fn as_contiguous(&self) -> Option<ContiguousSlice> {
+        if !self.is_contiguous() {
+            return None;
+        }
+        let range = self.range();
+        Some(ContiguousSlice {
+            start: range.start,
+            end: range.end,
+        })
+    }
+}
+
+/// The positions selected by a slice with a step of 1 or -1, in ascending order.
+///
+/// This `struct` is created by the `as_contiguous` method on `NormalizedSlice`. As a
+/// `RangeBounds<usize>`, it can be passed straight to std APIs such as `Vec::drain` or
+/// `BTreeMap::range`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContiguousSlice {
+    start: usize,
+    end: usize,
+}
+
+impl ContiguousSlice {
+    /// Returns the selected positions.
+    pub const fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+impl RangeBounds<usize> for ContiguousSlice {
+    fn start_bound(&self) -> Bound<&usize> {
+        Bound::Included(&self.start)
+    }
+
+    fn end_bound(&self) -> Bound<&usize> {
+        Bound::Excluded(&self.end)
+    }
+}
+
+/// A slice resolved against arrays of a given length, which can be applied to any of them
+/// without resolving the slice again.
+///
+/// This `struct` is created by the `prepare` method on `Slice`.
+#[derive(Debug, Clone)]
+pub struct PreparedSlice {
+    len: usize,
+    indices: Iter,
+}
+
+impl PreparedSlice {
+    /// Returns the length of the arrays the slice was prepared for.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the slice was prepared for empty arrays.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of selected elements.
+    pub fn count(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns an iterator that yields the selected positions, in selection order.
+    pub fn indices(&self) -> impl ExactSizeIterator<Item = usize> {
+        self.indices.clone()
+    }
+
+    /// Returns true if the position `i` is selected, in constant time.
+    pub fn contains(&self, i: usize) -> bool {
+        self.indices.contains(i)
+    }
+
+    /// Returns an iterator that yields the selected elements of `arr`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `arr` isn't the one the slice was prepared for.
+    pub fn apply<'a, T>(&self, arr: &'a [T]) -> impl Iterator<Item = &'a T> + 'a {
+        assert_eq!(arr.len(), self.len, "slice prepared for a different length");
+        Elements::new(arr, self.indices.clone())
+    }
+}
+
+/// A slice paired with the length of an array, which iterates over the selected positions.
+///
+/// This `struct` is created by the `over` method on `Slice`.
+#[derive(Debug, Clone, Copy)]
+pub struct Over<'a> {
+    slice: &'a Slice,
+    len: usize,
+}
+
+impl<'a> IntoIterator for Over<'a> {
+    type Item = usize;
+    type IntoIter = Indices;
+
+    fn into_iter(self) -> Indices {
+        Indices(self.slice.indices(self.len))
+    }
+}
+
+impl<'a, 'b> IntoIterator for &'b Over<'a> {
+    type Item = usize;
+    type IntoIter = Indices;
+
+    fn into_iter(self) -> Indices {
+        (*self).into_iter()
+    }
 }
 
+/// An iterator over the positions selected by a slice, in selection order.
+///
+/// This `struct` is created by iterating over the value returned by the `over` method on
+/// `Slice`.
+#[derive(Debug, Clone)]
+pub struct Indices(Iter);
+
+impl Iterator for Indices {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.0.count()
+    }
+
+    fn last(self) -> Option<usize> {
+        self.0.last()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<usize> {
+        self.0.nth(n)
+    }
+}
+
+impl ExactSizeIterator for Indices {}
+
+/// An iterator that counts from an initial number, yielding a given number of values.
+/// The direction and stride of the iteration are fixed.
+#[derive(Debug, Clone)]
 struct Iter {
-    i: i128,
-    end: i128,
-    step: i128,
+    next: usize,
+    remaining: usize,
+    stride: usize,
+    forward: bool,
+    /// The stride in the direction of the iteration, modulo `usize::MAX + 1`: adding it with
+    /// `wrapping_add` moves in either direction without branching on `forward`.
+    delta: usize,
+}
+
+impl Iter {
+    /// Returns the number of remaining values, in constant time.
+    fn len(&self) -> usize {
+        self.remaining
+    }
+
+    /// Returns true if the iteration yields `i`, in constant time.
+    fn contains(&self, i: usize) -> bool {
+        let distance = match self.forward {
+            true if i >= self.next => i - self.next,
+            false if i <= self.next => self.next - i,
+            _ => return false,
+        };
+        self.remaining > 0 && distance % self.stride == 0 && distance / self.stride < self.remaining
+    }
 }
 
-/// An iterator that counts from an initial number until a final limit.
-/// The direction and stride of the iteration can be controlled by the step parameter.
-/// A zero step produces an empty iteration.
 impl Iterator for Iter {
     type Item = usize;
 
     fn next(&mut self) -> Option<usize> {
-        if self.step == 0 {
+        if self.remaining == 0 {
             return None;
         }
 
-        let step = self.step;
-        let is_in_range = |a, b| if step >= 0 { a < b } else { a > b };
-        let i = self.i;
-        self.i += self.step;
+        let i = self.next;
+        self.remaining -= 1;
+        // stepping past the last value can wrap around, but it's never yielded.
+        self.next = i.wrapping_add(self.delta);
+        Some(i)
+    }
 
-        if is_in_range(i, self.end) {
-            Some(i as usize)
-        } else {
-            None
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+
+    fn count(self) -> usize {
+        self.remaining
+    }
+
+    fn last(self) -> Option<usize> {
+        match self.remaining {
+            0 => None,
+            n => Some(self.next.wrapping_add((n - 1).wrapping_mul(self.delta))),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<usize> {
+        if n >= self.remaining {
+            self.remaining = 0;
+            return None;
+        }
+        self.next = self.next.wrapping_add(n.wrapping_mul(self.delta));
+        self.remaining -= n;
+        self.next()
+    }
+}
+
+impl ExactSizeIterator for Iter {}
+
+/// The elements of an array selected by a slice.
+///
+/// Contiguous selections, i.e. with a step of 1 or -1, delegate to the iterator of the selected
+/// sub-slice, which the compiler optimizes much better than the generic strided walk.
+enum Elements<'a, T> {
+    Forward(core::slice::Iter<'a, T>),
+    Backward(core::iter::Rev<core::slice::Iter<'a, T>>),
+    Strided(&'a [T], Iter),
+}
+
+impl<'a, T> Elements<'a, T> {
+    /// Returns the elements of `arr` at the positions yielded by `it`, which must come from
+    /// `Slice::indices` called with the length of `arr`.
+    fn new(arr: &'a [T], it: Iter) -> Self {
+        match (it.len(), it.stride, it.forward) {
+            (0, _, _) => Elements::Forward([].iter()),
+            (n, 1, true) => Elements::Forward(arr[it.next..it.next + n].iter()),
+            (n, 1, false) => Elements::Backward(arr[it.next + 1 - n..=it.next].iter().rev()),
+            _ => Elements::Strided(arr, it),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Elements<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match self {
+            Elements::Forward(it) => it.next(),
+            Elements::Backward(it) => it.next(),
+            Elements::Strided(arr, it) => it.next().map(|i| get(arr, i)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Elements::Forward(it) => it.size_hint(),
+            Elements::Backward(it) => it.size_hint(),
+            Elements::Strided(_, it) => it.size_hint(),
+        }
+    }
+
+    // internal iteration (used by `collect`, `sum`, `for_each`...) dispatches on the variant
+    // once, rather than once per element.
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, &'a T) -> B,
+    {
+        match self {
+            Elements::Forward(it) => it.fold(init, f),
+            Elements::Backward(it) => it.fold(init, f),
+            Elements::Strided(arr, it) => it.map(|i| get(arr, i)).fold(init, f),
         }
     }
 }
@@ -231,20 +1229,127 @@ impl From<usize> for Index {
 
 impl From<isize> for Index {
     fn from(i: isize) -> Self {
-        if i < 0 {
-            Tail(-i as usize)
-        } else {
-            Head(i as usize)
+        Index::from_isize(i)
+    }
+}
+
+/// Implements `From` for signed integers, mapping negative values to `Tail` indices and
+/// saturating magnitudes that don't fit in a `usize` to `usize::MAX`. `i128` converts with
+/// `TryFrom` instead.
+macro_rules! from_signed {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Index {
+            /// Converts a non-negative integer to `Head` and a negative one to `Tail`, saturating
+            /// to `Head(usize::MAX)` or `Tail(usize::MAX)` if the magnitude doesn't fit in a
+            /// `usize`.
+            fn from(i: $t) -> Self {
+                let n = usize::try_from(i.unsigned_abs()).unwrap_or(usize::MAX);
+                if i < 0 {
+                    Tail(n)
+                } else {
+                    Head(n)
+                }
+            }
         }
+    )*};
+}
+
+from_signed!(i8, i16, i32, i64);
+
+/// Implements `From` for unsigned integers, saturating values that don't fit in a `usize` to
+/// `usize::MAX`. `u128` converts with `TryFrom` instead.
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Index {
+            /// Converts an integer to `Head`, saturating to `Head(usize::MAX)` if it doesn't fit
+            /// in a `usize`.
+            fn from(i: $t) -> Self {
+                Head(usize::try_from(i).unwrap_or(usize::MAX))
+            }
+        }
+    )*};
+}
+
+from_unsigned!(u8, u16, u32, u64);
+
+impl TryFrom<i128> for Index {
+    type Error = Error;
+
+    /// Converts a non-negative integer to `Head` and a negative one to `Tail`, returning
+    /// `Error::Overflow` if the magnitude doesn't fit in a `usize`.
+    ///
+    /// # Example
+    /// ```
+    /// use core::convert::TryFrom;
+    /// use slyce::{Error, Index};
+    /// assert_eq!(Index::try_from(-2i128), Ok(Index::Tail(2)));
+    /// assert_eq!(Index::try_from(i128::MIN), Err(Error::Overflow));
+    /// ```
+    fn try_from(i: i128) -> Result<Self, Error> {
+        let n = usize::try_from(i.unsigned_abs()).map_err(|_| Error::Overflow)?;
+        Ok(if i < 0 { Tail(n) } else { Head(n) })
     }
 }
 
-impl From<i32> for Index {
-    fn from(i: i32) -> Self {
-        if i < 0 {
-            Tail(-i as usize)
-        } else {
-            Head(i as usize)
+impl TryFrom<u128> for Index {
+    type Error = Error;
+
+    /// Converts an integer to `Head`, returning `Error::Overflow` if it doesn't fit in a
+    /// `usize`.
+    fn try_from(i: u128) -> Result<Self, Error> {
+        usize::try_from(i).map(Head).map_err(|_| Error::Overflow)
+    }
+}
+
+impl Add<usize> for Index {
+    type Output = Index;
+
+    /// Moves the index `k` positions towards the end of the array.
+    ///
+    /// A `Tail` index can't tell how far past the end of the array it would go, so it saturates
+    /// at `Tail(0)`, the position right after the last element. Slicing clamps positions past
+    /// the end to that same position, so the result selects the same elements. `Default` is
+    /// left as is.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Index;
+    /// assert_eq!(Index::Head(1) + 2, Index::Head(3));
+    /// assert_eq!(Index::Tail(3) + 2, Index::Tail(1));
+    /// assert_eq!(Index::Tail(1) + 2, Index::Tail(0));
+    /// ```
+    fn add(self, k: usize) -> Self {
+        match self {
+            Head(n) => Head(n.saturating_add(k)),
+            Tail(n) => Tail(n.saturating_sub(k)),
+            Default => Default,
+        }
+    }
+}
+
+impl Sub<usize> for Index {
+    type Output = Index;
+
+    /// Moves the index `k` positions towards the start of the array.
+    ///
+    /// A `Head` index can't become negative, as that would denote a position relative to the
+    /// end: moving it before the first element yields `Tail(usize::MAX)`, which slicing clamps
+    /// to the position right before the first element for any array shorter than `usize::MAX`.
+    /// `Default` is left as is.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Index;
+    /// assert_eq!(Index::Head(3) - 2, Index::Head(1));
+    /// assert_eq!(Index::Tail(1) - 2, Index::Tail(3));
+    /// assert_eq!(Index::Head(1) - 2, Index::Tail(usize::MAX));
+    /// ```
+    fn sub(self, k: usize) -> Self {
+        match self {
+            Head(n) if k > n => Tail(usize::MAX),
+            Head(n) => Head(n - k),
+            Tail(n) => Tail(n.saturating_add(k)),
+            Default => Default,
         }
     }
 }
@@ -258,16 +1363,47 @@ where
     }
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for Index {
     fn default() -> Self {
         Index::Default
     }
 }
 
+/// Returns the slices with small, omitted or past-the-end bounds and small or omitted steps,
+/// each paired with every small array length, for the tests that check an implementation
+/// against an independent one.
+#[cfg(test)]
+pub(crate) fn differential_cases() -> impl Iterator<Item = (usize, Slice)> {
+    let bounds = || {
+        (-10isize..10)
+            .map(Index::from)
+            .chain(Some(Index::Tail(0)))
+            .chain(Some(Index::Default))
+    };
+    let steps = || (-8..9).map(Some).chain(Some(None));
+    (0..8).flat_map(move |len| {
+        bounds().flat_map(move |start| {
+            bounds().flat_map(move |end| {
+                let start = start.clone();
+                steps().map(move |step| (len, Slice::new(start.clone(), end.clone(), step)))
+            })
+        })
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    /// Values far outside of the tested arrays, relative to the target pointer width so that the
+    /// tests behave the same on 16, 32 and 64 bit targets.
+    const HUGE: isize = isize::MAX;
+    const BIG: isize = isize::MAX / 4;
+
+    /// An empty selection, spelled out so that its type doesn't depend on the enabled features.
+    const NONE: Vec<usize> = Vec::new();
+
     fn slicer(len: usize) -> impl Fn(Option<isize>, Option<isize>, Option<isize>) -> Vec<usize> {
         move |start: Option<isize>, end: Option<isize>, step: Option<isize>| {
             let (start, end) = (start.into(), end.into());
@@ -284,50 +1420,50 @@ mod test {
         assert_eq!(s(Some(1), None, None), vec![1, 2, 3]);
         assert_eq!(s(Some(2), None, None), vec![2, 3]);
         assert_eq!(s(Some(3), None, None), vec![3]);
-        assert_eq!(s(Some(4), None, None), vec![]);
-        assert_eq!(s(Some(5), None, None), vec![]);
+        assert_eq!(s(Some(4), None, None), NONE);
+        assert_eq!(s(Some(5), None, None), NONE);
 
         assert_eq!(s(Some(0), Some(4), None), vec![0, 1, 2, 3]);
         assert_eq!(s(Some(1), Some(4), None), vec![1, 2, 3]);
         assert_eq!(s(Some(2), Some(4), None), vec![2, 3]);
         assert_eq!(s(Some(3), Some(4), None), vec![3]);
-        assert_eq!(s(Some(4), Some(4), None), vec![]);
-        assert_eq!(s(Some(5), Some(4), None), vec![]);
+        assert_eq!(s(Some(4), Some(4), None), NONE);
+        assert_eq!(s(Some(5), Some(4), None), NONE);
 
         assert_eq!(s(Some(0), Some(3), None), vec![0, 1, 2]);
         assert_eq!(s(Some(1), Some(3), None), vec![1, 2]);
         assert_eq!(s(Some(2), Some(3), None), vec![2]);
-        assert_eq!(s(Some(3), Some(3), None), vec![]);
-        assert_eq!(s(Some(4), Some(3), None), vec![]);
-        assert_eq!(s(Some(5), Some(3), None), vec![]);
+        assert_eq!(s(Some(3), Some(3), None), NONE);
+        assert_eq!(s(Some(4), Some(3), None), NONE);
+        assert_eq!(s(Some(5), Some(3), None), NONE);
 
         assert_eq!(s(Some(0), Some(2), None), vec![0, 1]);
         assert_eq!(s(Some(1), Some(2), None), vec![1]);
-        assert_eq!(s(Some(2), Some(2), None), vec![]);
-        assert_eq!(s(Some(3), Some(2), None), vec![]);
-        assert_eq!(s(Some(4), Some(2), None), vec![]);
-        assert_eq!(s(Some(5), Some(2), None), vec![]);
+        assert_eq!(s(Some(2), Some(2), None), NONE);
+        assert_eq!(s(Some(3), Some(2), None), NONE);
+        assert_eq!(s(Some(4), Some(2), None), NONE);
+        assert_eq!(s(Some(5), Some(2), None), NONE);
 
         assert_eq!(s(Some(0), Some(1), None), vec![0]);
-        assert_eq!(s(Some(1), Some(1), None), vec![]);
-        assert_eq!(s(Some(2), Some(1), None), vec![]);
-        assert_eq!(s(Some(3), Some(1), None), vec![]);
-        assert_eq!(s(Some(4), Some(1), None), vec![]);
-        assert_eq!(s(Some(5), Some(1), None), vec![]);
-
-        assert_eq!(s(Some(0), Some(0), None), vec![]);
-        assert_eq!(s(Some(1), Some(0), None), vec![]);
-        assert_eq!(s(Some(2), Some(0), None), vec![]);
-        assert_eq!(s(Some(3), Some(0), None), vec![]);
-        assert_eq!(s(Some(4), Some(0), None), vec![]);
-        assert_eq!(s(Some(5), Some(0), None), vec![]);
+        assert_eq!(s(Some(1), Some(1), None), NONE);
+        assert_eq!(s(Some(2), Some(1), None), NONE);
+        assert_eq!(s(Some(3), Some(1), None), NONE);
+        assert_eq!(s(Some(4), Some(1), None), NONE);
+        assert_eq!(s(Some(5), Some(1), None), NONE);
+
+        assert_eq!(s(Some(0), Some(0), None), NONE);
+        assert_eq!(s(Some(1), Some(0), None), NONE);
+        assert_eq!(s(Some(2), Some(0), None), NONE);
+        assert_eq!(s(Some(3), Some(0), None), NONE);
+        assert_eq!(s(Some(4), Some(0), None), NONE);
+        assert_eq!(s(Some(5), Some(0), None), NONE);
     }
 
     #[test]
     fn negative_start() {
         let s = slicer(4);
 
-        assert_eq!(s(Some(-113667776004), None, None), vec![0, 1, 2, 3]);
+        assert_eq!(s(Some(isize::MIN), None, None), vec![0, 1, 2, 3]);
         assert_eq!(s(Some(-6), None, None), vec![0, 1, 2, 3]);
         assert_eq!(s(Some(-5), None, None), vec![0, 1, 2, 3]);
         assert_eq!(s(Some(-4), None, None), vec![0, 1, 2, 3]);
@@ -345,25 +1481,25 @@ mod test {
         assert_eq!(s(Some(-4), Some(3), None), vec![0, 1, 2]);
         assert_eq!(s(Some(-3), Some(3), None), vec![1, 2]);
         assert_eq!(s(Some(-2), Some(3), None), vec![2]);
-        assert_eq!(s(Some(-1), Some(3), None), vec![]);
+        assert_eq!(s(Some(-1), Some(3), None), NONE);
 
         assert_eq!(s(Some(-5), Some(2), None), vec![0, 1]);
         assert_eq!(s(Some(-4), Some(2), None), vec![0, 1]);
         assert_eq!(s(Some(-3), Some(2), None), vec![1]);
-        assert_eq!(s(Some(-2), Some(2), None), vec![]);
-        assert_eq!(s(Some(-1), Some(2), None), vec![]);
+        assert_eq!(s(Some(-2), Some(2), None), NONE);
+        assert_eq!(s(Some(-1), Some(2), None), NONE);
 
         assert_eq!(s(Some(-5), Some(1), None), vec![0]);
         assert_eq!(s(Some(-4), Some(1), None), vec![0]);
-        assert_eq!(s(Some(-3), Some(1), None), vec![]);
-        assert_eq!(s(Some(-2), Some(1), None), vec![]);
-        assert_eq!(s(Some(-1), Some(1), None), vec![]);
-
-        assert_eq!(s(Some(-5), Some(0), None), vec![]);
-        assert_eq!(s(Some(-4), Some(0), None), vec![]);
-        assert_eq!(s(Some(-3), Some(0), None), vec![]);
-        assert_eq!(s(Some(-2), Some(0), None), vec![]);
-        assert_eq!(s(Some(-1), Some(0), None), vec![]);
+        assert_eq!(s(Some(-3), Some(1), None), NONE);
+        assert_eq!(s(Some(-2), Some(1), None), NONE);
+        assert_eq!(s(Some(-1), Some(1), None), NONE);
+
+        assert_eq!(s(Some(-5), Some(0), None), NONE);
+        assert_eq!(s(Some(-4), Some(0), None), NONE);
+        assert_eq!(s(Some(-3), Some(0), None), NONE);
+        assert_eq!(s(Some(-2), Some(0), None), NONE);
+        assert_eq!(s(Some(-1), Some(0), None), NONE);
     }
 
     #[test]
@@ -375,52 +1511,52 @@ mod test {
         assert_eq!(s(Some(1), None, None), vec![1, 2, 3]);
         assert_eq!(s(Some(2), None, None), vec![2, 3]);
         assert_eq!(s(Some(3), None, None), vec![3]);
-        assert_eq!(s(Some(4), None, None), vec![]);
-        assert_eq!(s(Some(5), None, None), vec![]);
+        assert_eq!(s(Some(4), None, None), NONE);
+        assert_eq!(s(Some(5), None, None), NONE);
 
         assert_eq!(s(Some(0), Some(4), None), vec![0, 1, 2, 3]);
         assert_eq!(s(Some(1), Some(4), None), vec![1, 2, 3]);
         assert_eq!(s(Some(2), Some(4), None), vec![2, 3]);
         assert_eq!(s(Some(3), Some(4), None), vec![3]);
-        assert_eq!(s(Some(4), Some(4), None), vec![]);
-        assert_eq!(s(Some(5), Some(4), None), vec![]);
+        assert_eq!(s(Some(4), Some(4), None), NONE);
+        assert_eq!(s(Some(5), Some(4), None), NONE);
 
         assert_eq!(s(Some(0), Some(-1), None), vec![0, 1, 2]);
         assert_eq!(s(Some(1), Some(-1), None), vec![1, 2]);
         assert_eq!(s(Some(2), Some(-1), None), vec![2]);
-        assert_eq!(s(Some(3), Some(-1), None), vec![]);
-        assert_eq!(s(Some(4), Some(-1), None), vec![]);
-        assert_eq!(s(Some(5), Some(-1), None), vec![]);
+        assert_eq!(s(Some(3), Some(-1), None), NONE);
+        assert_eq!(s(Some(4), Some(-1), None), NONE);
+        assert_eq!(s(Some(5), Some(-1), None), NONE);
 
         assert_eq!(s(Some(0), Some(-2), None), vec![0, 1]);
         assert_eq!(s(Some(1), Some(-2), None), vec![1]);
-        assert_eq!(s(Some(2), Some(-2), None), vec![]);
-        assert_eq!(s(Some(3), Some(-2), None), vec![]);
-        assert_eq!(s(Some(4), Some(-2), None), vec![]);
-        assert_eq!(s(Some(5), Some(-2), None), vec![]);
+        assert_eq!(s(Some(2), Some(-2), None), NONE);
+        assert_eq!(s(Some(3), Some(-2), None), NONE);
+        assert_eq!(s(Some(4), Some(-2), None), NONE);
+        assert_eq!(s(Some(5), Some(-2), None), NONE);
 
         assert_eq!(s(Some(0), Some(-3), None), vec![0]);
-        assert_eq!(s(Some(1), Some(-3), None), vec![]);
-        assert_eq!(s(Some(2), Some(-3), None), vec![]);
-        assert_eq!(s(Some(3), Some(-3), None), vec![]);
-        assert_eq!(s(Some(4), Some(-3), None), vec![]);
-        assert_eq!(s(Some(5), Some(-3), None), vec![]);
-
-        assert_eq!(s(Some(0), Some(-4), None), vec![]);
-        assert_eq!(s(Some(1), Some(-4), None), vec![]);
-        assert_eq!(s(Some(2), Some(-4), None), vec![]);
-        assert_eq!(s(Some(3), Some(-4), None), vec![]);
-        assert_eq!(s(Some(4), Some(-4), None), vec![]);
-        assert_eq!(s(Some(5), Some(-4), None), vec![]);
-
-        assert_eq!(s(Some(0), Some(-5), None), vec![]);
-        assert_eq!(s(Some(1), Some(-5), None), vec![]);
-        assert_eq!(s(Some(2), Some(-5), None), vec![]);
-        assert_eq!(s(Some(3), Some(-5), None), vec![]);
-        assert_eq!(s(Some(4), Some(-5), None), vec![]);
-        assert_eq!(s(Some(5), Some(-5), None), vec![]);
-
-        assert_eq!(s(Some(5), Some(-113667776004), None), vec![]);
+        assert_eq!(s(Some(1), Some(-3), None), NONE);
+        assert_eq!(s(Some(2), Some(-3), None), NONE);
+        assert_eq!(s(Some(3), Some(-3), None), NONE);
+        assert_eq!(s(Some(4), Some(-3), None), NONE);
+        assert_eq!(s(Some(5), Some(-3), None), NONE);
+
+        assert_eq!(s(Some(0), Some(-4), None), NONE);
+        assert_eq!(s(Some(1), Some(-4), None), NONE);
+        assert_eq!(s(Some(2), Some(-4), None), NONE);
+        assert_eq!(s(Some(3), Some(-4), None), NONE);
+        assert_eq!(s(Some(4), Some(-4), None), NONE);
+        assert_eq!(s(Some(5), Some(-4), None), NONE);
+
+        assert_eq!(s(Some(0), Some(-5), None), NONE);
+        assert_eq!(s(Some(1), Some(-5), None), NONE);
+        assert_eq!(s(Some(2), Some(-5), None), NONE);
+        assert_eq!(s(Some(3), Some(-5), None), NONE);
+        assert_eq!(s(Some(4), Some(-5), None), NONE);
+        assert_eq!(s(Some(5), Some(-5), None), NONE);
+
+        assert_eq!(s(Some(5), Some(isize::MIN), None), NONE);
     }
 
     #[test]
@@ -431,9 +1567,9 @@ mod test {
         assert_eq!(s(Some(1), Some(6), None), vec![1, 2, 3]);
         assert_eq!(s(Some(2), Some(6), None), vec![2, 3]);
         assert_eq!(s(Some(3), Some(6), None), vec![3]);
-        assert_eq!(s(Some(4), Some(6), None), vec![]);
-        assert_eq!(s(Some(5), Some(6), None), vec![]);
-        assert_eq!(s(Some(4294967296), Some(17179869184), None), vec![]);
+        assert_eq!(s(Some(4), Some(6), None), NONE);
+        assert_eq!(s(Some(5), Some(6), None), NONE);
+        assert_eq!(s(Some(BIG), Some(HUGE), None), NONE);
     }
 
     #[test]
@@ -445,20 +1581,20 @@ mod test {
         assert_eq!(s(Some(1), Some(4), Some(2)), vec![1, 3]);
         assert_eq!(s(Some(2), Some(4), Some(2)), vec![2]);
         assert_eq!(s(Some(3), Some(4), Some(2)), vec![3]);
-        assert_eq!(s(Some(4), Some(4), Some(2)), vec![]);
+        assert_eq!(s(Some(4), Some(4), Some(2)), NONE);
 
-        assert_eq!(s(Some(0), Some(4), Some(17179869184)), vec![0]);
-        assert_eq!(s(Some(1), Some(4), Some(17179869184)), vec![1]);
-        assert_eq!(s(Some(2), Some(4), Some(17179869184)), vec![2]);
-        assert_eq!(s(Some(3), Some(4), Some(17179869184)), vec![3]);
-        assert_eq!(s(Some(4), Some(4), Some(17179869184)), vec![]);
+        assert_eq!(s(Some(0), Some(4), Some(HUGE)), vec![0]);
+        assert_eq!(s(Some(1), Some(4), Some(HUGE)), vec![1]);
+        assert_eq!(s(Some(2), Some(4), Some(HUGE)), vec![2]);
+        assert_eq!(s(Some(3), Some(4), Some(HUGE)), vec![3]);
+        assert_eq!(s(Some(4), Some(4), Some(HUGE)), NONE);
     }
 
     #[test]
     fn zero_step() {
         let s = slicer(4);
 
-        assert_eq!(s(Some(3), None, Some(0)), vec![]);
+        assert_eq!(s(Some(3), None, Some(0)), NONE);
     }
 
     #[test]
@@ -469,21 +1605,21 @@ mod test {
         assert_eq!(s(Some(3), Some(0), Some(-1)), vec![3, 2, 1]);
         assert_eq!(s(Some(3), Some(1), Some(-1)), vec![3, 2]);
         assert_eq!(s(Some(3), Some(2), Some(-1)), vec![3]);
-        assert_eq!(s(Some(3), Some(3), Some(-1)), vec![]);
+        assert_eq!(s(Some(3), Some(3), Some(-1)), NONE);
 
         assert_eq!(s(Some(3), None, Some(-2)), vec![3, 1]);
         assert_eq!(s(Some(3), Some(0), Some(-2)), vec![3, 1]);
         assert_eq!(s(Some(3), Some(1), Some(-2)), vec![3]);
         assert_eq!(s(Some(3), Some(2), Some(-2)), vec![3]);
-        assert_eq!(s(Some(3), Some(3), Some(-2)), vec![]);
+        assert_eq!(s(Some(3), Some(3), Some(-2)), NONE);
 
-        assert_eq!(s(Some(3), None, Some(-17179869184)), vec![3]);
-        assert_eq!(s(Some(3), Some(0), Some(-17179869184)), vec![3]);
-        assert_eq!(s(Some(3), Some(1), Some(-17179869184)), vec![3]);
-        assert_eq!(s(Some(3), Some(2), Some(-17179869184)), vec![3]);
-        assert_eq!(s(Some(3), Some(3), Some(-17179869184)), vec![]);
+        assert_eq!(s(Some(3), None, Some(-HUGE)), vec![3]);
+        assert_eq!(s(Some(3), Some(0), Some(-HUGE)), vec![3]);
+        assert_eq!(s(Some(3), Some(1), Some(-HUGE)), vec![3]);
+        assert_eq!(s(Some(3), Some(2), Some(-HUGE)), vec![3]);
+        assert_eq!(s(Some(3), Some(3), Some(-HUGE)), NONE);
 
-        assert_eq!(s(Some(17179869184), None, Some(-1)), vec![3, 2, 1, 0]);
+        assert_eq!(s(Some(HUGE), None, Some(-1)), vec![3, 2, 1, 0]);
         assert_eq!(s(Some(5), None, Some(-1)), vec![3, 2, 1, 0]);
         assert_eq!(s(Some(4), None, Some(-1)), vec![3, 2, 1, 0]);
     }
@@ -496,8 +1632,8 @@ mod test {
         assert_eq!(s(Some(-2), None, Some(-1)), vec![2, 1, 0]);
         assert_eq!(s(Some(-3), None, Some(-1)), vec![1, 0]);
         assert_eq!(s(Some(-4), None, Some(-1)), vec![0]);
-        assert_eq!(s(Some(-5), None, Some(-1)), vec![]);
-        assert_eq!(s(Some(-17179869184), None, Some(-1)), vec![]);
+        assert_eq!(s(Some(-5), None, Some(-1)), NONE);
+        assert_eq!(s(Some(-HUGE), None, Some(-1)), NONE);
     }
 
     #[test]
@@ -508,27 +1644,611 @@ mod test {
         assert_eq!(s(Some(3), Some(-4), Some(-1)), vec![3, 2, 1]);
         assert_eq!(s(Some(3), Some(-3), Some(-1)), vec![3, 2]);
         assert_eq!(s(Some(3), Some(-2), Some(-1)), vec![3]);
-        assert_eq!(s(Some(3), Some(-1), Some(-1)), vec![]);
+        assert_eq!(s(Some(3), Some(-1), Some(-1)), NONE);
 
         assert_eq!(s(Some(3), Some(-5), Some(-2)), vec![3, 1]);
         assert_eq!(s(Some(3), Some(-4), Some(-2)), vec![3, 1]);
         assert_eq!(s(Some(3), Some(-3), Some(-2)), vec![3]);
         assert_eq!(s(Some(3), Some(-2), Some(-2)), vec![3]);
-        assert_eq!(s(Some(3), Some(-1), Some(-2)), vec![]);
+        assert_eq!(s(Some(3), Some(-1), Some(-2)), NONE);
 
-        assert_eq!(s(Some(3), Some(-5), Some(-17179869184)), vec![3]);
-        assert_eq!(s(Some(3), Some(-4), Some(-17179869184)), vec![3]);
-        assert_eq!(s(Some(3), Some(-3), Some(-17179869184)), vec![3]);
-        assert_eq!(s(Some(3), Some(-2), Some(-17179869184)), vec![3]);
-        assert_eq!(s(Some(3), Some(-1), Some(-17179869184)), vec![]);
+        assert_eq!(s(Some(3), Some(-5), Some(-HUGE)), vec![3]);
+        assert_eq!(s(Some(3), Some(-4), Some(-HUGE)), vec![3]);
+        assert_eq!(s(Some(3), Some(-3), Some(-HUGE)), vec![3]);
+        assert_eq!(s(Some(3), Some(-2), Some(-HUGE)), vec![3]);
+        assert_eq!(s(Some(3), Some(-1), Some(-HUGE)), NONE);
     }
 
     #[test]
     fn empty_array() {
         let s = slicer(0);
 
-        assert_eq!(s(None, None, None), vec![]);
-        assert_eq!(s(None, None, Some(-1)), vec![]);
+        assert_eq!(s(None, None, None), NONE);
+        assert_eq!(s(None, None, Some(-1)), NONE);
+    }
+
+    #[test]
+    fn apply_as_ref() {
+        let s = Slice {
+            start: Index::Tail(2),
+            end: Index::Default,
+            step: None,
+        };
+        let render = |it: &mut dyn Iterator<Item = &i32>| it.copied().collect::<Vec<_>>();
+
+        assert_eq!(render(&mut s.apply(&[1, 2, 3])), vec![2, 3]);
+        assert_eq!(render(&mut s.apply(&vec![1, 2, 3])), vec![2, 3]);
+        assert_eq!(render(&mut s.apply(&[1, 2, 3][..])), vec![2, 3]);
+        let b: Box<[i32]> = vec![1, 2, 3].into_boxed_slice();
+        assert_eq!(render(&mut s.apply(&b)), vec![2, 3]);
+        let a: std::sync::Arc<[i32]> = vec![1, 2, 3].into();
+        assert_eq!(render(&mut s.apply(&a)), vec![2, 3]);
+    }
+
+    #[test]
+    fn apply_enumerated() {
+        let v = vec![10, 20, 30, 40, 50];
+        let s = Slice {
+            start: Index::Tail(2),
+            end: Index::Default,
+            step: Some(-2),
+        };
+        assert_eq!(
+            s.apply_enumerated(&v).collect::<Vec<_>>(),
+            vec![(3, &40), (1, &20)]
+        );
+    }
+
+    #[test]
+    fn apply_cloned() {
+        let v = vec![String::from("a"), String::from("b"), String::from("c")];
+        let s = Slice {
+            start: Index::Default,
+            end: Index::Default,
+            step: Some(-2),
+        };
+        assert_eq!(s.apply_cloned(&v).collect::<Vec<_>>(), vec!["c", "a"]);
+
+        let v = vec![10, 20, 30, 40];
+        assert_eq!(s.apply_copied(&v).collect::<Vec<_>>(), vec![40, 20]);
+    }
+
+    #[test]
+    fn apply_fn() {
+        let s: Slice = "[-2::-3]".parse().unwrap();
+        let mut calls = 0;
+        let r: Vec<usize> = s
+            .apply_fn(10, |i| {
+                calls += 1;
+                i * i
+            })
+            .collect();
+        assert_eq!(r, vec![64, 25, 4]);
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn apply_contiguous() {
+        for len in 0..6 {
+            let v: Vec<usize> = (0..len).collect();
+            for start in -7..7 {
+                for end in -7..7 {
+                    for step in [-2, -1, 1, 2] {
+                        let s = Slice::new(start.into(), end.into(), Some(step));
+                        let want: Vec<usize> = s.indices(len).collect();
+                        // both the external and the internal iteration.
+                        let mut it = s.apply(&v);
+                        let got: Vec<usize> = core::iter::from_fn(|| it.next().copied()).collect();
+                        assert_eq!(got, want, "{} on {}", s, len);
+                        let got: Vec<usize> = s.apply(&v).copied().collect();
+                        assert_eq!(got, want, "{} on {}", s, len);
+                        assert_eq!(s.apply(&v).size_hint(), (want.len(), Some(want.len())));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn prepare() {
+        let s: Slice = "[1::3]".parse().unwrap();
+        let p = s.prepare(10);
+        assert_eq!((p.len(), p.count()), (10, 3));
+        assert_eq!(p.indices().collect::<Vec<_>>(), [1, 4, 7]);
+        assert_eq!(
+            (p.contains(4), p.contains(5), p.contains(10)),
+            (true, false, false)
+        );
+        // the same prepared slice applies to every array of its length.
+        let (a, b): (Vec<usize>, Vec<char>) = ((10..20).collect(), ('a'..='j').collect());
+        assert_eq!(p.apply(&a).collect::<Vec<_>>(), [&11, &14, &17]);
+        assert_eq!(p.apply(&b).collect::<Vec<_>>(), [&'b', &'e', &'h']);
+
+        let p = "[::-2]".parse::<Slice>().unwrap().prepare(5);
+        assert_eq!(p.indices().collect::<Vec<_>>(), [4, 2, 0]);
+        assert_eq!((p.contains(0), p.contains(1)), (true, false));
+
+        let p = "[::0]".parse::<Slice>().unwrap().prepare(5);
+        assert_eq!((p.count(), p.contains(0)), (0, false));
+        assert!("[::]".parse::<Slice>().unwrap().prepare(0).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "slice prepared for a different length")]
+    fn prepare_length_mismatch() {
+        let s: Slice = "[::]".parse().unwrap();
+        s.prepare(3).apply(&[1, 2]).count();
+    }
+
+    #[test]
+    fn over() {
+        let s: Slice = "[1::3]".parse().unwrap();
+        let over = s.over(10);
+        let mut got = vec![];
+        for i in &over {
+            got.push(i);
+        }
+        assert_eq!(got, vec![1, 4, 7]);
+        assert_eq!(over.into_iter().len(), 3);
+        assert_eq!(over.into_iter().last(), Some(7));
+        assert_eq!(s.over(0).into_iter().next(), None);
+    }
+
+    #[test]
+    fn iter_len() {
+        for len in 0..6 {
+            for start in -7..7 {
+                for end in -7..7 {
+                    for step in -7..7 {
+                        let (start, end) = (Some(start).into(), Some(end).into());
+                        let it = Slice {
+                            start,
+                            end,
+                            step: Some(step),
+                        }
+                        .indices(len);
+                        // fold walks the iteration, unlike the closed-form overrides.
+                        let all: Vec<usize> = it.clone().fold(Vec::new(), |mut v, i| {
+                            v.push(i);
+                            v
+                        });
+                        assert_eq!(it.len(), all.len());
+                        assert_eq!(it.clone().count(), all.len());
+                        assert_eq!(it.clone().last(), all.last().copied());
+                        for n in 0..all.len() + 2 {
+                            let mut nth = it.clone();
+                            assert_eq!(nth.nth(n), <[usize]>::get(&all, n).copied());
+                            assert_eq!(nth.collect::<Vec<_>>(), all[(n + 1).min(all.len())..]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fill() {
+        let filled = |expr: &str| {
+            let mut v = [0; 6];
+            expr.parse::<Slice>().unwrap().fill(&mut v, 1);
+            v
+        };
+        assert_eq!(filled("[::]"), [1, 1, 1, 1, 1, 1]);
+        assert_eq!(filled("[1:-2:]"), [0, 1, 1, 1, 0, 0]);
+        assert_eq!(filled("[::-4]"), [0, 1, 0, 0, 0, 1]);
+        assert_eq!(filled("[-3:0:-2]"), [0, 1, 0, 1, 0, 0]);
+        assert_eq!(filled("[9::]"), [0; 6]);
+        assert_eq!(filled("[::0]"), [0; 6]);
+        let mut v = vec![String::new(); 3];
+        Slice::new(Index::Tail(2), Index::Default, None).fill(&mut v, "x".to_string());
+        assert_eq!(v, vec!["", "x", "x"]);
+    }
+
+    #[test]
+    fn map_in_place() {
+        // returns the array after the call and the elements visited, in order.
+        let mapped = |expr: &str| {
+            let mut v = [0, 1, 2, 3, 4, 5];
+            let mut visited = vec![];
+            expr.parse::<Slice>().unwrap().map_in_place(&mut v, |x| {
+                visited.push(*x);
+                *x += 10;
+            });
+            (v, visited)
+        };
+        assert_eq!(mapped("[1:3:]"), ([0, 11, 12, 3, 4, 5], vec![1, 2]));
+        assert_eq!(mapped("[::-2]"), ([0, 11, 2, 13, 4, 15], vec![5, 3, 1]));
+        assert_eq!(mapped("[-2::-3]"), ([0, 11, 2, 3, 14, 5], vec![4, 1]));
+        assert_eq!(mapped("[4:1:]"), ([0, 1, 2, 3, 4, 5], vec![]));
+        assert_eq!(mapped("[::0]"), ([0, 1, 2, 3, 4, 5], vec![]));
+    }
+
+    #[test]
+    fn apply_mut() {
+        let mut v = vec![10, 20, 30, 40, 50];
+
+        let s = Slice {
+            start: Index::Tail(2),
+            end: Index::Default,
+            step: Some(-2),
+        };
+        for (n, x) in s.apply_mut(&mut v).enumerate() {
+            *x += n;
+        }
+        assert_eq!(v, vec![10, 21, 30, 40, 50]);
+
+        let s = Slice {
+            start: Index::Default,
+            end: Index::Default,
+            step: Some(2),
+        };
+        s.apply_mut(&mut v).for_each(|x| *x = 0);
+        assert_eq!(v, vec![0, 21, 0, 40, 0]);
+    }
+
+    #[test]
+    fn swap_slices() {
+        fn s(start: Option<isize>, end: Option<isize>, step: Option<isize>) -> Slice {
+            let (start, end) = (start.into(), end.into());
+            Slice { start, end, step }
+        }
+
+        let mut v = vec![0, 1, 2, 3, 4, 5];
+        let evens = s(None, None, Some(2));
+        assert_eq!(evens.swap_slices(&s(None, None, Some(-2)), &mut v), Ok(()));
+        assert_eq!(v, vec![5, 4, 3, 2, 1, 0]);
+
+        assert_eq!(
+            evens.swap_slices(&s(None, Some(2), None), &mut v),
+            Err(Error::LengthMismatch {
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            evens.swap_slices(&s(Some(1), Some(4), None), &mut v),
+            Err(Error::Overlap { index: 2 })
+        );
+        assert_eq!(v, vec![5, 4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn const_eval() {
+        const S: Slice = Slice::new(Index::from_isize(-2), Index::Default, Some(-2));
+        const COUNT: usize = S.count(5);
+        const INDICES: [Option<usize>; COUNT + 1] = [S.nth(5, 0), S.nth(5, 1), S.nth(5, 2)];
+
+        assert_eq!(INDICES, [Some(3), Some(1), None]);
+        for len in 0..6 {
+            let all: Vec<_> = S.indices(len).collect();
+            assert_eq!(S.count(len), all.len());
+            for (n, &i) in all.iter().enumerate() {
+                assert_eq!(S.nth(len, n), Some(i));
+            }
+        }
+    }
+
+    #[test]
+    fn huge_len() {
+        let max = usize::MAX;
+        let s = |start, end, step| Slice { start, end, step }.indices(max);
+        let min_step = isize::MIN as usize;
+        let max_step = isize::MAX as usize;
+
+        assert_eq!(
+            s(Tail(2), Default, None).collect::<Vec<_>>(),
+            vec![max - 2, max - 1]
+        );
+        assert_eq!(s(Tail(max), Tail(0), Some(1)).len(), max);
+        assert_eq!(s(Tail(max), Tail(0), Some(1)).last(), Some(max - 1));
+        assert_eq!(s(Default, Default, Some(-1)).nth(max - 2), Some(1));
+        assert_eq!(s(Default, Tail(max - 1), Some(-1)).len(), max - 2);
+        assert_eq!(s(Head(max), Head(0), Some(-1)).len(), max - 1);
+        assert_eq!(
+            s(Default, Default, Some(isize::MIN)).collect::<Vec<_>>(),
+            vec![max - 1, max - 1 - min_step]
+        );
+        assert_eq!(
+            s(Head(1), Default, Some(isize::MAX)).collect::<Vec<_>>(),
+            vec![1, 1 + max_step]
+        );
+    }
+
+    #[test]
+    fn from_int() {
+        assert_eq!(Index::from(0isize), Head(0));
+        assert_eq!(Index::from(-1isize), Tail(1));
+        assert_eq!(Index::from(isize::MAX), Head(isize::MAX as usize));
+        assert_eq!(Index::from(isize::MIN), Tail(isize::MIN as usize));
+
+        let saturated = |n: u32| usize::try_from(n).unwrap_or(usize::MAX);
+        assert_eq!(Index::from(-1i32), Tail(1));
+        assert_eq!(Index::from(i32::MAX), Head(saturated(i32::MAX as u32)));
+        assert_eq!(Index::from(i32::MIN), Tail(saturated(1 << 31)));
+
+        let saturated = |n: u128| usize::try_from(n).unwrap_or(usize::MAX);
+        assert_eq!(Index::from(-3i8), Tail(3));
+        assert_eq!(Index::from(i16::MIN), Tail(1 << 15));
+        assert_eq!(Index::from(u8::MAX), Head(255));
+        assert_eq!(Index::from(-1i64), Tail(1));
+        assert_eq!(Index::from(i64::MAX), Head(saturated(i64::MAX as u128)));
+        assert_eq!(Index::from(i64::MIN), Tail(saturated(1 << 63)));
+        assert_eq!(Index::from(u64::MAX), Head(saturated(u64::MAX as u128)));
+    }
+
+    #[test]
+    fn resolve() {
+        assert_eq!(Head(0).resolve(3), Some(0));
+        assert_eq!(Head(2).resolve(3), Some(2));
+        assert_eq!(Head(3).resolve(3), None);
+        assert_eq!(Tail(1).resolve(3), Some(2));
+        assert_eq!(Tail(3).resolve(3), Some(0));
+        assert_eq!(Tail(4).resolve(3), None);
+        assert_eq!(Tail(0).resolve(3), None);
+        assert_eq!(Index::Default.resolve(3), None);
+        assert_eq!(Head(0).resolve(0), None);
+        assert_eq!(Tail(usize::MAX).resolve(usize::MAX), Some(0));
+    }
+
+    #[test]
+    fn index_arithmetic() {
+        for len in 0..6usize {
+            let indices = (0..8).map(Head).chain((0..8).map(Tail));
+            for i in indices {
+                // the position the index denotes, which may be outside the array.
+                let pos = match i {
+                    Head(n) => n as isize,
+                    Tail(n) => len as isize - n as isize,
+                    Default => unreachable!(),
+                };
+                for k in -10..10isize {
+                    let moved = i.clone().offset_by(k);
+                    let want = (pos + k).max(-1).min(len as isize + 1);
+                    for &forward in &[true, false] {
+                        let bound = |p: isize| {
+                            let p = if forward { p } else { p + 1 };
+                            Some(p.max(0).min(len as isize) as usize)
+                        };
+                        assert_eq!(
+                            moved.to_bound(len, forward),
+                            bound(want),
+                            "{:?} offset by {} on {} elements ({:?})",
+                            i,
+                            k,
+                            len,
+                            moved
+                        );
+                    }
+                }
+            }
+        }
+        assert_eq!(Index::Default + 1, Index::Default);
+        assert_eq!(Index::Default - 1, Index::Default);
+        assert_eq!(Head(usize::MAX) + 1, Head(usize::MAX));
+        assert_eq!(Tail(usize::MAX) - 1, Tail(usize::MAX));
+    }
+
+    #[test]
+    fn cmp_with_len() {
+        use core::cmp::Ordering::*;
+
+        assert_eq!(Head(0).cmp_with_len(&Tail(3), 3), Equal);
+        assert_eq!(Tail(1).cmp_with_len(&Head(1), 3), Greater);
+        assert_eq!(Tail(5).cmp_with_len(&Head(0), 3), Less);
+        assert_eq!(Head(4).cmp_with_len(&Tail(0), 3), Greater);
+        assert_eq!(
+            Tail(usize::MAX).cmp_with_len(&Head(usize::MAX), usize::MAX),
+            Less
+        );
+        assert_eq!(Head(usize::MAX).cmp_with_len(&Tail(1), 0), Greater);
+        assert_eq!(Tail(2).cmp_with_len(&Tail(7), 3), Greater);
+
+        let mut v = vec![Tail(1), Head(3), Tail(4), Head(0)];
+        v.sort_by(|a, b| a.cmp_with_len(b, 4));
+        assert_eq!(v, [Tail(4), Head(0), Tail(1), Head(3)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index::Default can't be compared")]
+    fn cmp_with_len_default() {
+        Head(0).cmp_with_len(&Index::Default, 3);
+    }
+
+    #[test]
+    fn bounds() {
+        // the naive iteration that a container implementing its own slicing would do.
+        let naive = |s: &Slice, len: usize| {
+            let (start, end, forward) = s.bounds(len);
+            let stride = s.step.unwrap_or(1).unsigned_abs();
+            let mut r = Vec::new();
+            if stride != 0 && forward {
+                r.extend((start..end).step_by(stride));
+            } else if stride != 0 {
+                r.extend((end..start).rev().step_by(stride));
+            }
+            r
+        };
+        for (len, s) in crate::differential_cases() {
+            let want: Vec<usize> = s.indices(len).collect();
+            assert_eq!(naive(&s, len), want, "{} on {}", s, len);
+        }
+    }
+
+    #[test]
+    fn step_enum() {
+        assert_eq!(Step::from(None), Step::Default);
+        assert_eq!(Step::from(Some(-3)), Step::Value(-3));
+        assert_eq!(Option::<isize>::from(Step::Value(0)), Some(0));
+        assert_eq!(Step::default().to_option(), None);
+        assert_eq!(Step::Default.get(), 1);
+        assert_eq!(Step::Value(-1).get(), -1);
+
+        for expr in &["[::]", "[::-1]", "[::0]", "[1:2:3]"] {
+            let s: Slice = expr.parse().unwrap();
+            let step = Step::from(s.step);
+            assert_eq!(step.get(), s.step_or_default());
+            assert_eq!(step.to_string().parse::<Step>(), Ok(step));
+        }
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let indices = [
+            Head(0),
+            Head(3),
+            Head(usize::MAX),
+            Tail(0),
+            Tail(1),
+            Tail(4),
+            Tail(usize::MAX),
+            Index::Default,
+        ];
+        let steps = [
+            None,
+            Some(0),
+            Some(1),
+            Some(-1),
+            Some(3),
+            Some(isize::MIN),
+            Some(isize::MAX),
+        ];
+        for start in &indices {
+            for end in &indices {
+                for &step in &steps {
+                    let s = Slice::new(start.clone(), end.clone(), step);
+                    let expr = s.to_string();
+                    let parsed: Slice = expr.parse().unwrap();
+                    assert_eq!(parsed.to_string(), expr);
+                    for len in 0..8 {
+                        assert!(
+                            parsed.indices(len).eq(s.indices(len)),
+                            "{} on {}",
+                            expr,
+                            len
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn first_and_last_index() {
+        let ends = |expr: &str, len| {
+            let s: Slice = expr.parse().unwrap();
+            (s.first_index(len), s.last_index(len))
+        };
+        assert_eq!(ends("[::]", 10), (Some(0), Some(9)));
+        assert_eq!(ends("[1::3]", 10), (Some(1), Some(7)));
+        assert_eq!(ends("[2:8:3]", 10), (Some(2), Some(5)));
+        assert_eq!(ends("[::-1]", 10), (Some(9), Some(0)));
+        assert_eq!(ends("[-1:2:-3]", 10), (Some(9), Some(3)));
+        assert_eq!(ends("[20::-2]", 10), (Some(9), Some(1)));
+        assert_eq!(ends("[3:4:]", 10), (Some(3), Some(3)));
+        assert_eq!(ends("[5:5:]", 10), (None, None));
+        assert_eq!(ends("[::0]", 10), (None, None));
+        assert_eq!(ends("[::-1]", 0), (None, None));
+        let s = Slice::new(Index::Default, Index::Default, Some(-1));
+        assert_eq!(s.first_index(usize::MAX), Some(usize::MAX - 1));
+        assert_eq!(s.last_index(usize::MAX), Some(0));
+    }
+
+    #[test]
+    fn view_descriptor() {
+        let desc = |expr: &str, len| {
+            let d = expr.parse::<Slice>().unwrap().view_descriptor(len)?;
+            Some((d.offset, d.stride, d.count))
+        };
+        assert_eq!(desc("[::]", 10), Some((0, 1, 10)));
+        assert_eq!(desc("[2:8:3]", 10), Some((2, 3, 2)));
+        assert_eq!(desc("[3:4:]", 10), Some((3, 1, 1)));
+        assert_eq!(desc("[::-1]", 4), Some((3, -1, 4)));
+        assert_eq!(desc("[-1:0:-3]", 10), Some((9, -3, 3)));
+        assert_eq!(desc("[20::-4]", 10), Some((9, -4, 3)));
+        assert_eq!(desc("[5:5:]", 10), None);
+        assert_eq!(desc("[::0]", 10), None);
+        assert_eq!(desc("[::]", 0), None);
+    }
+
+    #[test]
+    fn empty_reason() {
+        let reason = |expr: &str, len| expr.parse::<Slice>().unwrap().empty_reason(len);
+        assert_eq!(reason("[::0]", 0), Some(EmptyReason::ZeroStep));
+        assert_eq!(reason("[::]", 0), Some(EmptyReason::EmptyInput));
+        assert_eq!(reason("[7:]", 5), Some(EmptyReason::StartOutOfBounds));
+        assert_eq!(reason("[-7::-1]", 5), Some(EmptyReason::StartOutOfBounds));
+        assert_eq!(reason("[3:1]", 5), Some(EmptyReason::EndBeforeStart));
+        assert_eq!(reason("[1:3:-1]", 5), Some(EmptyReason::EndBeforeStart));
+        assert_eq!(reason("[:-7]", 5), Some(EmptyReason::EndBeforeStart));
+        assert_eq!(reason("[1:3]", 5), None);
+        assert_eq!(reason("[-9:1]", 5), None);
+        assert_eq!(reason("[9::-1]", 5), None);
+        assert_eq!(reason("[4:3:-9]", 5), None);
+
+        let v = [1, 2, 3, 4, 5];
+        let s: Slice = "[::-2]".parse().unwrap();
+        assert_eq!(s.try_apply(&v).unwrap().collect::<Vec<_>>(), [&5, &3, &1]);
+        let s: Slice = "[:-7]".parse().unwrap();
+        assert_eq!(s.try_apply(&v).err(), Some(EmptyReason::EndBeforeStart));
+    }
+
+    #[test]
+    fn normalize() {
+        let v = [0, 1, 2, 3, 4, 5];
+        let norm = |expr: &str| expr.parse::<Slice>().unwrap().normalize(v.len());
+        let contiguous = |expr| norm(expr).as_contiguous().map(|c| c.range());
+        assert_eq!(contiguous("[1:-1:]"), Some(1..5));
+        assert_eq!(contiguous("[-2::-1]"), Some(0..5));
+        assert_eq!(contiguous("[-20:20:]"), Some(0..6));
+        assert_eq!(contiguous("[4:1:]"), Some(4..4));
+        assert_eq!(contiguous("[1:4:-1]"), Some(2..2));
+
+        let s = norm("[1::2]");
+        assert!(!s.is_contiguous());
+        assert_eq!(s.range(), 1..6);
+        assert_eq!(s.as_contiguous(), None);
+
+        // as range bounds, for std APIs.
+        let mut d = v.to_vec();
+        let drained: Vec<_> = d.drain(norm("[2:4:]").as_contiguous().unwrap()).collect();
+        assert_eq!((drained, d), (vec![2, 3], vec![0, 1, 4, 5]));
+        let m: std::collections::BTreeMap<usize, char> = (0..6).zip('a'..).collect();
+        let c = norm("[-3:]").as_contiguous().unwrap();
+        assert_eq!(m.range(c).map(|(_, &c)| c).collect::<String>(), "def");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn mask_string() {
+        let mask = |expr: &str, len| expr.parse::<Slice>().unwrap().mask_string(len);
+        assert_eq!(mask("[::]", 5), "#####");
+        assert_eq!(mask("[1::2]", 5), ".#.#.");
+        assert_eq!(mask("[::-2]", 5), "#.#.#");
+        assert_eq!(mask("[-2::]", 5), "...##");
+        assert_eq!(mask("[2:2:]", 5), ".....");
+        assert_eq!(mask("[::0]", 3), "...");
+        assert_eq!(mask("[::]", 0), "");
+    }
+
+    #[test]
+    fn try_from_int() {
+        assert_eq!(Index::try_from(0i128), Ok(Head(0)));
+        assert_eq!(Index::try_from(-1i128), Ok(Tail(1)));
+        assert_eq!(Index::try_from(i128::from(i64::MIN)), Ok(Tail(1 << 63)));
+        assert_eq!(Index::try_from(usize::MAX as i128), Ok(Head(usize::MAX)));
+        assert_eq!(Index::try_from(-(usize::MAX as i128)), Ok(Tail(usize::MAX)));
+        assert_eq!(
+            Index::try_from(usize::MAX as i128 + 1),
+            Err(Error::Overflow)
+        );
+        assert_eq!(Index::try_from(i128::MIN), Err(Error::Overflow));
+
+        assert_eq!(Index::try_from(u128::from(u8::MAX)), Ok(Head(255)));
+        assert_eq!(Index::try_from(usize::MAX as u128), Ok(Head(usize::MAX)));
+        assert_eq!(
+            Index::try_from(usize::MAX as u128 + 1),
+            Err(Error::Overflow)
+        );
     }
 
     #[test]
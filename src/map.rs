@@ -0,0 +1,54 @@
+//! Slicing of `indexmap` maps and sets by insertion order.
+
+use crate::Slice;
+use indexmap::{IndexMap, IndexSet};
+
+impl Slice {
+    /// Returns an iterator that yields the entries of `m` that match the slice expression, where
+    /// positions follow the order of the map.
+    pub fn apply_map<'a, K, V, S>(
+        &self,
+        m: &'a IndexMap<K, V, S>,
+    ) -> impl Iterator<Item = (&'a K, &'a V)> + 'a {
+        self.indices(m.len())
+            .map(move |i| m.get_index(i).expect("index within bounds"))
+    }
+
+    /// Returns an iterator that yields the values of `s` that match the slice expression, where
+    /// positions follow the order of the set.
+    pub fn apply_set<'a, T, S>(&self, s: &'a IndexSet<T, S>) -> impl Iterator<Item = &'a T> + 'a {
+        self.indices(s.len())
+            .map(move |i| s.get_index(i).expect("index within bounds"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::hash_map::RandomState;
+
+    #[test]
+    fn apply_map() {
+        let m: IndexMap<&str, i32, RandomState> = [("a", 1), ("b", 2), ("c", 3), ("d", 4)]
+            .iter()
+            .copied()
+            .collect();
+        let s: Slice = "[-2::]".parse().unwrap();
+        assert_eq!(
+            s.apply_map(&m).collect::<Vec<_>>(),
+            [(&"c", &3), (&"d", &4)]
+        );
+        let s: Slice = "[::-3]".parse().unwrap();
+        assert_eq!(
+            s.apply_map(&m).collect::<Vec<_>>(),
+            [(&"d", &4), (&"a", &1)]
+        );
+    }
+
+    #[test]
+    fn apply_set() {
+        let s: IndexSet<char, RandomState> = "hello".chars().collect();
+        let sel: Slice = "[1::2]".parse().unwrap();
+        assert_eq!(sel.apply_set(&s).collect::<String>(), "eo");
+    }
+}
@@ -0,0 +1,278 @@
+//! Slicing of multi-dimensional data stored as nested sequences.
+
+use crate::{Index, Slice};
+use alloc::vec::Vec;
+
+/// A slice for each axis of multi-dimensional data.
+///
+/// Axes that are not specified select everything, as if they were `[::]`.
+///
+/// # Example
+/// ```
+/// use slyce::{MultiSlice, Slice};
+/// let table = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+/// let rows: Slice = "[::2]".parse()?;
+/// let cols: Slice = "[::-1]".parse()?;
+/// let m = MultiSlice::new(vec![rows, cols]);
+/// assert_eq!(m.apply(&table), vec![vec![&3, &2, &1], vec![&9, &8, &7]]);
+/// # Ok::<(), slyce::ParseError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MultiSlice {
+    pub axes: Vec<Slice>,
+}
+
+impl MultiSlice {
+    /// Returns a multi-slice with the given slices, the first of which applies to the outermost
+    /// axis.
+    pub fn new(axes: Vec<Slice>) -> Self {
+        MultiSlice { axes }
+    }
+
+    /// Returns the slice of an axis, which selects everything if it was not specified.
+    pub fn axis(&self, n: usize) -> Slice {
+        match self.axes.get(n) {
+            Some(s) => s.clone(),
+            None => Slice::new(Index::Default, Index::Default, None),
+        }
+    }
+
+    /// Returns the elements of two-dimensional data selected by the multi-slice: the rows are
+    /// selected by the first axis and, within each of them, the columns by the second.
+    ///
+    /// Rows don't need to have the same length; each is sliced according to its own.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the multi-slice has more than two axes.
+    pub fn apply<'a, T, R>(&self, rows: &'a [R]) -> Vec<Vec<&'a T>>
+    where
+        R: AsRef<[T]>,
+    {
+        assert!(
+            self.axes.len() <= 2,
+            "cannot apply {} axes to two-dimensional data",
+            self.axes.len()
+        );
+        let cols = self.axis(1);
+        self.axis(0)
+            .apply(rows)
+            .map(|row| cols.apply(row.as_ref()).collect())
+            .collect()
+    }
+
+    /// Returns an iterator that yields the elements of a flat, row-major buffer selected by the
+    /// multi-slice, row by row: the rows are selected by the first axis and, within each of
+    /// them, the columns by the second.
+    ///
+    /// Each row of `buf` has `stride` elements. Trailing elements that don't fill a whole row
+    /// are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the multi-slice has more than two axes or if `stride` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::{MultiSlice, Slice};
+    /// // a 3x4 image.
+    /// let pixels = [0, 1, 2, 3, 10, 11, 12, 13, 20, 21, 22, 23];
+    /// let m = MultiSlice::new(vec!["[::2]".parse()?, "[::-3]".parse()?]);
+    /// assert_eq!(m.apply_strided(&pixels, 4).collect::<Vec<_>>(), vec![&3, &0, &23, &20]);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn apply_strided<'a, T>(
+        &self,
+        buf: &'a [T],
+        stride: usize,
+    ) -> impl Iterator<Item = &'a T> + 'a {
+        let cols = self.axis(1);
+        self.strided_rows(buf, stride)
+            .flat_map(move |row| cols.indices(row.len()).map(move |i| &row[i]))
+    }
+
+    /// Returns an iterator that yields, for each row of a flat, row-major buffer selected by the
+    /// first axis, the sub-slice of the columns selected by the second axis, or `None` if the
+    /// second axis doesn't select a contiguous range in order (i.e. its step isn't 1).
+    ///
+    /// Each row of `buf` has `stride` elements. Trailing elements that don't fill a whole row
+    /// are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the multi-slice has more than two axes or if `stride` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::{MultiSlice, Slice};
+    /// let pixels = [0, 1, 2, 3, 10, 11, 12, 13, 20, 21, 22, 23];
+    /// let m = MultiSlice::new(vec!["[1::]".parse()?, "[1:-1:]".parse()?]);
+    /// let rows: Vec<&[i32]> = m.row_slices_strided(&pixels, 4).unwrap().collect();
+    /// assert_eq!(rows, vec![&[11, 12][..], &[21, 22][..]]);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn row_slices_strided<'a, T>(
+        &self,
+        buf: &'a [T],
+        stride: usize,
+    ) -> Option<impl Iterator<Item = &'a [T]> + 'a> {
+        let cols = self.axis(1);
+        if cols.step.unwrap_or(1) != 1 {
+            return None;
+        }
+        let (start, end, _) = cols.bounds(stride);
+        let end = end.max(start);
+        Some(
+            self.strided_rows(buf, stride)
+                .map(move |row| &row[start..end]),
+        )
+    }
+
+    /// Returns the rows of `buf` selected by the first axis.
+    fn strided_rows<'a, T>(
+        &self,
+        buf: &'a [T],
+        stride: usize,
+    ) -> impl Iterator<Item = &'a [T]> + 'a {
+        assert!(
+            self.axes.len() <= 2,
+            "cannot apply {} axes to two-dimensional data",
+            self.axes.len()
+        );
+        assert!(stride > 0, "row stride must be non-zero");
+        self.axis(0)
+            .indices(buf.len() / stride)
+            .map(move |r| &buf[r * stride..(r + 1) * stride])
+    }
+}
+
+/// What a numpy-style multi-axis expression selects along one axis.
+#[derive(Debug, Clone)]
+pub enum AxisSelector {
+    /// A single position, which removes the axis.
+    Index(Index),
+    /// A range of positions.
+    Slice(Slice),
+    /// As many full slices as needed to cover the axes that are not otherwise selected.
+    Ellipsis,
+}
+
+impl AxisSelector {
+    /// Replaces the ellipsis in `axes` with the full slices needed to select `ndim` axes in
+    /// total, and appends them if there's no ellipsis.
+    ///
+    /// Returns `None` if `axes` selects more than `ndim` axes.
+    pub fn expand(axes: &[AxisSelector], ndim: usize) -> Option<Vec<AxisSelector>> {
+        let explicit = axes
+            .iter()
+            .filter(|a| !matches!(a, AxisSelector::Ellipsis))
+            .count();
+        let missing = ndim.checked_sub(explicit)?;
+        let full = || AxisSelector::Slice(Slice::new(Index::Default, Index::Default, None));
+        let mut r = Vec::with_capacity(ndim);
+        let mut expanded = false;
+        for a in axes {
+            match a {
+                AxisSelector::Ellipsis => {
+                    r.extend((0..missing).map(|_| full()));
+                    expanded = true;
+                }
+                a => r.push(a.clone()),
+            }
+        }
+        if !expanded {
+            r.extend((0..missing).map(|_| full()));
+        }
+        Some(r)
+    }
+}
+
+impl From<Vec<Slice>> for MultiSlice {
+    fn from(axes: Vec<Slice>) -> Self {
+        MultiSlice::new(axes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn s(expr: &str) -> Slice {
+        expr.parse().unwrap()
+    }
+
+    #[test]
+    fn apply() {
+        let table = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let m = MultiSlice::new(vec![s("[1::]"), s("[:-1:]")]);
+        assert_eq!(m.apply(&table), vec![vec![&4, &5], vec![&7, &8]]);
+
+        let m = MultiSlice::new(vec![s("[::-2]")]);
+        assert_eq!(m.apply(&table), vec![vec![&7, &8, &9], vec![&1, &2, &3]]);
+
+        let m = MultiSlice::default();
+        assert_eq!(m.apply(&table).len(), 3);
+    }
+
+    #[test]
+    fn apply_ragged_slices() {
+        let a: &[i32] = &[1, 2, 3];
+        let b: &[i32] = &[4];
+        let c: &[i32] = &[];
+        let m = MultiSlice::new(vec![s("[::]"), s("[-2::]")]);
+        assert_eq!(m.apply(&[a, b, c]), vec![vec![&2, &3], vec![&4], vec![]]);
+    }
+
+    #[test]
+    fn apply_strided() {
+        let table = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let buf: Vec<i32> = table.concat();
+        for rows in &["[::]", "[1::]", "[::-2]", "[5::]"] {
+            for cols in &["[::]", "[:-1:]", "[::-2]", "[2:1:]"] {
+                let m = MultiSlice::new(vec![s(rows), s(cols)]);
+                let want: Vec<&i32> = m.apply(&table).concat();
+                let got: Vec<&i32> = m.apply_strided(&buf, 3).collect();
+                assert_eq!(got, want, "{} {}", rows, cols);
+                if let Some(rows) = m.row_slices_strided(&buf, 3) {
+                    assert_eq!(rows.flatten().collect::<Vec<_>>(), want);
+                }
+            }
+        }
+
+        // a trailing partial row is ignored.
+        let m = MultiSlice::new(vec![s("[-1::]")]);
+        assert_eq!(
+            m.apply_strided(&buf[..8], 3).collect::<Vec<_>>(),
+            vec![&4, &5, &6]
+        );
+        assert!(MultiSlice::new(vec![s("[::]"), s("[::2]")])
+            .row_slices_strided(&buf, 3)
+            .is_none());
+    }
+
+    #[test]
+    fn expand() {
+        let axes = AxisSelector::parse_list("[1, ..., ::2]").unwrap();
+        let e = AxisSelector::expand(&axes, 4).unwrap();
+        let names: Vec<String> = e
+            .iter()
+            .map(|a| match a {
+                AxisSelector::Index(i) => i.to_string(),
+                AxisSelector::Slice(s) => s.to_string(),
+                AxisSelector::Ellipsis => "...".to_string(),
+            })
+            .collect();
+        assert_eq!(names, ["1", "[::]", "[::]", "[::2]"]);
+
+        assert_eq!(AxisSelector::expand(&axes, 2).unwrap().len(), 2);
+        assert!(AxisSelector::expand(&axes, 1).is_none());
+        assert_eq!(AxisSelector::expand(&axes[..1], 3).unwrap().len(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn apply_too_many_axes() {
+        let m = MultiSlice::new(vec![s("[::]"), s("[::]"), s("[::]")]);
+        m.apply::<i32, Vec<i32>>(&[]);
+    }
+}
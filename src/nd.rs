@@ -0,0 +1,131 @@
+//! Conversions between `Slice` and `ndarray` slices.
+//!
+//! The two disagree on the meaning of a negative step: ndarray first restricts an axis to the
+//! range `start..end` and then walks it backwards, while a python slice walks backwards from
+//! `start` down to `end`. Python slices also clamp out of range indices, which make ndarray
+//! panic. A `Slice` is therefore resolved against the length of the axis before being
+//! converted.
+
+use crate::{Index, Slice};
+use core::convert::TryFrom;
+use ndarray::SliceInfoElem;
+
+impl Slice {
+    /// Returns the ndarray slice that selects the same elements of an axis of length `len`.
+    ///
+    /// Use `ndarray::SliceInfoElem::from` to build multi-axis slices out of it.
+    pub fn to_ndarray(&self, len: usize) -> ndarray::Slice {
+        let step = self.step_or_default();
+        let (first, last) = match (self.first_index(len), self.last_index(len)) {
+            (Some(first), Some(last)) => (first as isize, last as isize),
+            _ => return ndarray::Slice::new(0, Some(0), 1),
+        };
+        if step > 0 {
+            ndarray::Slice::new(first, Some(last + 1), step)
+        } else {
+            ndarray::Slice::new(last, Some(first + 1), step)
+        }
+    }
+}
+
+impl From<ndarray::Slice> for Slice {
+    /// Returns the slice that selects the same elements as an ndarray slice, as long as the
+    /// latter is within the bounds of the axis it's applied to.
+    fn from(s: ndarray::Slice) -> Self {
+        if s.step > 0 {
+            return Slice {
+                start: s.start.into(),
+                end: s.end.into(),
+                step: Some(s.step),
+            };
+        }
+        // walk the range `s.start..s.end` backwards: the python slice starts right before the
+        // end and stops right before the start.
+        let start = match s.end {
+            None => Index::Default,
+            // nothing is selected, but `Head(0)` would select the first element.
+            Some(0) => {
+                return Slice {
+                    start: Index::Head(0),
+                    end: Index::Head(0),
+                    step: Some(s.step),
+                }
+            }
+            Some(n) if n > 0 => Index::Head(n as usize - 1),
+            Some(n) => Index::Tail(n.unsigned_abs() + 1),
+        };
+        let end = match s.start {
+            0 => Index::Default,
+            n if n > 0 => Index::Head(n as usize - 1),
+            n => Index::Tail(n.unsigned_abs() + 1),
+        };
+        Slice {
+            start,
+            end,
+            step: Some(s.step),
+        }
+    }
+}
+
+impl TryFrom<SliceInfoElem> for Slice {
+    type Error = SliceInfoElem;
+
+    /// Converts the slice of an axis, returning the element back if it's a single index or a
+    /// new axis.
+    fn try_from(elem: SliceInfoElem) -> Result<Self, Self::Error> {
+        match elem {
+            SliceInfoElem::Slice { start, end, step } => {
+                Ok(ndarray::Slice::new(start, end, step).into())
+            }
+            _ => Err(elem),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ndarray::{s, Array1};
+
+    #[test]
+    fn to_ndarray() {
+        for (len, s) in crate::differential_cases() {
+            let a: Array1<usize> = (0..len).collect();
+            let want: Vec<usize> = s.apply(a.as_slice().unwrap()).copied().collect();
+            let got = a.slice(s![s.to_ndarray(len)]).to_vec();
+            assert_eq!(got, want, "{} on {:?}", s, a);
+        }
+    }
+
+    #[test]
+    fn from_ndarray() {
+        for len in 0..8isize {
+            let a: Array1<isize> = (0..len).collect();
+            let v = a.as_slice().unwrap();
+            let ends = (-9..9).map(Some).chain(Some(None));
+            for start in -9..9 {
+                for end in ends.clone() {
+                    for step in (-4..4).filter(|&s| s != 0) {
+                        // ndarray panics when indices are out of bounds.
+                        let valid = |i: isize| -len <= i && i <= len;
+                        if !valid(start) || !end.map_or(true, valid) {
+                            continue;
+                        }
+                        let n = ndarray::Slice::new(start, end, step);
+                        let want = a.slice(s![n]).to_vec();
+                        let got: Vec<isize> = Slice::from(n).apply(v).copied().collect();
+                        assert_eq!(got, want, "{:?} on {:?}", n, a);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from_slice_info_elem() {
+        let s = Slice::try_from(SliceInfoElem::from(ndarray::Slice::new(1, None, -2))).unwrap();
+        assert_eq!(s.to_string(), "[:0:-2]");
+        assert!(Slice::try_from(SliceInfoElem::Index(1)).is_err());
+        assert!(Slice::try_from(SliceInfoElem::NewAxis).is_err());
+    }
+}
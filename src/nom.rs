@@ -0,0 +1,146 @@
+//! A [nom](https://docs.rs/nom) combinator for slice expressions, to embed them in larger
+//! grammars such as JSONPath or a query language.
+//!
+//! # Example
+//! ```
+//! use nom::bytes::complete::tag;
+//! use nom::sequence::pair;
+//! use slyce::Slice;
+//!
+//! let (rest, (name, s)) = pair(tag::<_, _, nom::error::Error<_>>("$.items"), slyce::nom::slice)(
+//!     "$.items[1:-1].name",
+//! )?;
+//! assert_eq!((name, rest), ("$.items", ".name"));
+//! assert_eq!(s.apply(&[1, 2, 3, 4]).collect::<Vec<_>>(), vec![&2, &3]);
+//! # Ok::<(), nom::Err<nom::error::Error<&str>>>(())
+//! ```
+
+use crate::{Index, Slice};
+use ::nom::character::complete::{char, digit1, multispace0};
+use ::nom::combinator::{map_res, opt, recognize};
+use ::nom::error::{FromExternalError, ParseError};
+use ::nom::sequence::{delimited, pair, preceded, tuple};
+use ::nom::IResult;
+use core::num::ParseIntError;
+
+/// Recognizes an optionally negative integer.
+fn integer<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, &'a str, E> {
+    recognize(pair(opt(char('-')), digit1))(input)
+}
+
+/// Parses an optional, optionally negative index; `-0` is the same as `0`, like in python.
+fn index<'a, E>(input: &'a str) -> IResult<&'a str, Index, E>
+where
+    E: ParseError<&'a str> + FromExternalError<&'a str, ParseIntError>,
+{
+    map_res(
+        opt(integer),
+        |i: Option<&str>| -> Result<Index, ParseIntError> {
+            let i = match i {
+                Some(i) => i,
+                None => return Ok(Index::Default),
+            };
+            let n = i.trim_start_matches('-').parse::<usize>()?;
+            Ok(if i.starts_with('-') && n != 0 {
+                Index::Tail(n)
+            } else {
+                Index::Head(n)
+            })
+        },
+    )(input)
+}
+
+/// Parses an optional, optionally negative step.
+fn step<'a, E>(input: &'a str) -> IResult<&'a str, Option<isize>, E>
+where
+    E: ParseError<&'a str> + FromExternalError<&'a str, ParseIntError>,
+{
+    map_res(opt(integer), |i: Option<&str>| {
+        i.map(str::parse).transpose()
+    })(input)
+}
+
+/// Parses what `f` parses, allowing blanks around it.
+fn blanks<'a, O, E: ParseError<&'a str>>(
+    f: impl FnMut(&'a str) -> IResult<&'a str, O, E>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O, E> {
+    delimited(multispace0, f, multispace0)
+}
+
+/// Parses a bracketed slice expression such as `[1:-1:2]`, with the same syntax as
+/// `Slice::from_str` except that the brackets are required and blanks after the closing
+/// bracket are left to the caller.
+///
+/// The second colon may be omitted along with the step, and blanks are allowed around the
+/// parts of the expression. Indices and steps that overflow are errors of kind `MapRes`.
+///
+/// # Example
+/// ```
+/// let (rest, s) = slyce::nom::slice::<nom::error::Error<_>>("[ -3 : ] + 1")?;
+/// assert_eq!(rest, " + 1");
+/// assert_eq!(s.to_string(), "[-3::]");
+/// # Ok::<(), nom::Err<nom::error::Error<&str>>>(())
+/// ```
+pub fn slice<'a, E>(input: &'a str) -> IResult<&'a str, Slice, E>
+where
+    E: ParseError<&'a str> + FromExternalError<&'a str, ParseIntError>,
+{
+    let (input, (start, _, end)) =
+        preceded(char('['), tuple((blanks(index), char(':'), blanks(index))))(input)?;
+    // a step that fails to parse after the second colon is an error, not a missing step.
+    let (input, step) = match opt(char(':'))(input)? {
+        (input, Some(_)) => blanks(step)(input)?,
+        (input, None) => (input, None),
+    };
+    let (input, _) = char(']')(input)?;
+    Ok((input, Slice { start, end, step }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::nom::error::{Error, ErrorKind};
+
+    fn parse(s: &str) -> IResult<&str, Slice, Error<&str>> {
+        slice(s)
+    }
+
+    #[test]
+    fn parse_slice() {
+        for expr in &[
+            "[::]",
+            "[1:]",
+            "[:-1]",
+            "[-0:2:-1]",
+            "[ 1 : 2 ]",
+            "[1:2:]",
+            "[\t-3 :: 2\n]",
+        ] {
+            let want: Slice = expr.parse().unwrap();
+            let (rest, got) = parse(expr).unwrap();
+            assert_eq!((rest, got.to_string()), ("", want.to_string()), "{}", expr);
+        }
+        assert_eq!(parse("[1:2]]").map(|(rest, _)| rest), Ok("]"));
+        assert_eq!(parse("[1:2] ").map(|(rest, _)| rest), Ok(" "));
+    }
+
+    #[test]
+    fn errors() {
+        let kind = |s| match parse(s) {
+            Err(::nom::Err::Error(e)) => (e.input, e.code),
+            r => panic!("{:?} parsed as {:?}", s, r),
+        };
+        assert_eq!(kind("1:2"), ("1:2", ErrorKind::Char));
+        assert_eq!(kind("[1]"), ("]", ErrorKind::Char));
+        assert_eq!(kind("[-:]"), ("-:]", ErrorKind::Char));
+        assert_eq!(kind("[1:2:3:]"), (":]", ErrorKind::Char));
+        assert_eq!(
+            kind("[99999999999999999999999:]"),
+            ("99999999999999999999999:]", ErrorKind::MapRes)
+        );
+        assert_eq!(
+            kind("[::99999999999999999999999]"),
+            ("99999999999999999999999]", ErrorKind::MapRes)
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! A reference implementation of python slicing semantics.
+//!
+//! This module transcribes the algorithm CPython uses to slice a list, without any of the
+//! tricks used by the rest of the crate to avoid overflows and allocations. It's meant to be
+//! obviously correct rather than fast, so that tests and fuzz targets can check `Slice` against
+//! it without a python installation.
+
+use crate::{Index, Slice};
+use alloc::vec::Vec;
+
+/// Returns the integer python would use for the index, or `None` if the index is omitted.
+///
+/// `Tail(0)` has no python spelling, as `-0` is the same as `0`; it denotes the position past
+/// the last element, so it's translated to `len`.
+fn to_python(i: &Index, len: usize) -> Option<i128> {
+    match *i {
+        Index::Head(n) => Some(n as i128),
+        Index::Tail(0) => Some(len as i128),
+        Index::Tail(n) => Some(-(n as i128)),
+        Index::Default => None,
+    }
+}
+
+/// Returns the indices that python would select when slicing a list of length `len`.
+///
+/// Python rejects a zero step, while `Slice` selects nothing; the oracle returns no indices.
+pub fn indices(slice: &Slice, len: usize) -> Vec<usize> {
+    let len = len as i128;
+    let step = slice.step.unwrap_or(1) as i128;
+    if step == 0 {
+        return Vec::new();
+    }
+
+    // see PySlice_AdjustIndices in CPython's Objects/sliceobject.c
+    let (lower, upper) = if step < 0 { (-1, len - 1) } else { (0, len) };
+    let adjust = |i: Option<i128>, default: i128| match i {
+        None => default,
+        Some(i) if i < 0 => (i + len).max(lower),
+        Some(i) => i.min(upper),
+    };
+    let start = adjust(
+        to_python(&slice.start, len as usize),
+        if step < 0 { upper } else { lower },
+    );
+    let stop = adjust(
+        to_python(&slice.end, len as usize),
+        if step < 0 { lower } else { upper },
+    );
+
+    let mut r = Vec::new();
+    let mut i = start;
+    while (step > 0 && i < stop) || (step < 0 && i > stop) {
+        r.push(i as usize);
+        i += step;
+    }
+    r
+}
+
+/// Returns the elements of `arr` that python would select.
+pub fn apply<'a, T>(slice: &Slice, arr: &'a [T]) -> Vec<&'a T> {
+    indices(slice, arr.len())
+        .into_iter()
+        .map(|i| &arr[i])
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn matches_python() {
+        let v = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let expr = |s: &str| {
+            apply(&s.parse().unwrap(), &v)
+                .into_iter()
+                .copied()
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(expr("[::]"), v);
+        assert_eq!(expr("[::-1]"), [9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
+        assert_eq!(expr("[-3::]"), [7, 8, 9]);
+        assert_eq!(expr("[8:1:-3]"), [8, 5, 2]);
+        assert_eq!(expr("[100:-100:-4]"), [9, 5, 1]);
+        assert_eq!(expr("[-100:100:4]"), [0, 4, 8]);
+        assert_eq!(expr("[5:5:]"), [0; 0]);
+        assert_eq!(expr("[::0]"), [0; 0]);
+    }
+
+    #[test]
+    fn matches_slice() {
+        for (len, s) in crate::differential_cases() {
+            let v: Vec<usize> = (0..len).collect();
+            let got: Vec<&usize> = s.apply(&v).collect();
+            assert_eq!(got, apply(&s, &v), "{} on {:?}", s, v);
+        }
+    }
+}
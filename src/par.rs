@@ -0,0 +1,38 @@
+//! Parallel slicing with rayon.
+
+use crate::Slice;
+use rayon::prelude::*;
+
+impl Slice {
+    /// Returns a parallel iterator that yields the elements that match the slice expression.
+    ///
+    /// The iterator is indexed, so the selection order (including the reversed order of
+    /// negative steps) is preserved by `collect` and friends.
+    pub fn par_apply<'a, T>(&self, arr: &'a [T]) -> impl IndexedParallelIterator<Item = &'a T> + 'a
+    where
+        T: Sync,
+    {
+        let it = self.indices(arr.len());
+        let (start, delta) = (it.next, it.delta);
+        // k < len, so moving by k strides stays within the array.
+        (0..it.len())
+            .into_par_iter()
+            .map(move |k| &arr[start.wrapping_add(k.wrapping_mul(delta))])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn par_apply() {
+        let v: Vec<usize> = (0..1000).collect();
+        for expr in &["[::]", "[::-1]", "[3:-7:5]", "[-3:7:-5]", "[::0]", "[5:1:]"] {
+            let s: Slice = expr.parse().unwrap();
+            let want: Vec<_> = s.apply(&v).collect();
+            let got: Vec<_> = s.par_apply(&v).collect();
+            assert_eq!(got, want, "{}", expr);
+        }
+    }
+}
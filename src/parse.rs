@@ -0,0 +1,817 @@
+//! Parsing of python-like slice expressions such as `[1:-1:2]`.
+
+use crate::{rfc9535, Index, Slice, Step};
+#[cfg(feature = "alloc")]
+use crate::{AxisSelector, MultiSlice, Selector};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+
+/// An error which can be returned when parsing a slice expression.
+///
+/// Besides its offset, the error tells what went wrong and, for unexpected input, which tokens
+/// would have been accepted there, so that the offending character can be pointed at:
+///
+/// ```
+/// use slyce::{ParseErrorKind, Slice, Token};
+/// let err = "[1 2]".parse::<Slice>().unwrap_err();
+/// assert_eq!(err.offset(), 3);
+/// assert_eq!(err.kind(), ParseErrorKind::Unexpected);
+/// assert_eq!(err.expected().collect::<Vec<_>>(), vec![Token::Colon]);
+/// assert_eq!(err.to_string(), "invalid slice expression at offset 3: expected ':'");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    offset: usize,
+    kind: ParseErrorKind,
+    expected: u8,
+}
+
+/// The reason why a slice expression couldn't be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input doesn't match any of the expected tokens.
+    Unexpected,
+    /// An integer is too large for an index or a step.
+    OutOfRange,
+    /// An integer is spelled in a way the grammar forbids, such as with leading zeros in RFC 9535.
+    InvalidInteger,
+    /// A multi-axis expression has more than one ellipsis.
+    DuplicateEllipsis,
+    /// An item of a multi-axis expression must be a slice but isn't.
+    NotASlice,
+}
+
+/// A token of the slice expression grammar, as reported by `ParseError::expected`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token {
+    /// `[`
+    OpenBracket,
+    /// `]`
+    CloseBracket,
+    /// `:`
+    Colon,
+    /// `,`
+    Comma,
+    /// `...`
+    Ellipsis,
+    /// An optionally signed integer.
+    Integer,
+    /// An identifier, made of ASCII letters, digits and underscores and not starting with a
+    /// digit.
+    Identifier,
+    /// The end of the input.
+    End,
+}
+
+impl Token {
+    const ALL: [Token; 8] = [
+        Token::OpenBracket,
+        Token::CloseBracket,
+        Token::Colon,
+        Token::Comma,
+        Token::Ellipsis,
+        Token::Integer,
+        Token::Identifier,
+        Token::End,
+    ];
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '[' => Some(Token::OpenBracket),
+            ']' => Some(Token::CloseBracket),
+            ':' => Some(Token::Colon),
+            ',' => Some(Token::Comma),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Token::OpenBracket => "'['",
+            Token::CloseBracket => "']'",
+            Token::Colon => "':'",
+            Token::Comma => "','",
+            Token::Ellipsis => "'...'",
+            Token::Integer => "an integer",
+            Token::Identifier => "an identifier",
+            Token::End => "end of input",
+        })
+    }
+}
+
+impl ParseError {
+    fn new(offset: usize, kind: ParseErrorKind) -> Self {
+        ParseError {
+            offset,
+            kind,
+            expected: 0,
+        }
+    }
+
+    /// Returns the byte offset in the input where parsing failed.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the reason why parsing failed.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the tokens that would have been accepted at the offset of the error.
+    ///
+    /// Only errors of kind `Unexpected` have expected tokens.
+    pub fn expected(&self) -> impl Iterator<Item = Token> {
+        let expected = self.expected;
+        Token::ALL
+            .iter()
+            .copied()
+            .filter(move |t| expected & t.bit() != 0)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid slice expression at offset {}", self.offset)?;
+        match self.kind {
+            ParseErrorKind::Unexpected => {
+                let n = self.expected().count();
+                for (i, token) in self.expected().enumerate() {
+                    let sep = match i {
+                        0 => ": expected ",
+                        i if i == n - 1 => " or ",
+                        _ => ", ",
+                    };
+                    write!(f, "{}{}", sep, token)?;
+                }
+                Ok(())
+            }
+            ParseErrorKind::OutOfRange => f.write_str(": integer out of range"),
+            ParseErrorKind::InvalidInteger => f.write_str(": invalid integer"),
+            ParseErrorKind::DuplicateEllipsis => f.write_str(": more than one ellipsis"),
+            ParseErrorKind::NotASlice => f.write_str(": expected a slice"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    /// The tokens that were tried and not found at `expected_at`.
+    expected: u8,
+    expected_at: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Parser {
+            input,
+            pos: 0,
+            expected: 0,
+            expected_at: 0,
+        }
+    }
+
+    /// Records that `token` would have been accepted at the current position.
+    fn expecting(&mut self, token: Token) {
+        if self.expected_at != self.pos {
+            self.expected = 0;
+            self.expected_at = self.pos;
+        }
+        self.expected |= token.bit();
+    }
+
+    /// Returns an error for unexpected input at the current position.
+    fn error(&self) -> ParseError {
+        ParseError {
+            expected: if self.expected_at == self.pos {
+                self.expected
+            } else {
+                0
+            },
+            ..ParseError::new(self.pos, ParseErrorKind::Unexpected)
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            if let Some(token) = Token::from_char(c) {
+                self.expecting(token);
+            }
+            false
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn eat_ellipsis(&mut self) -> bool {
+        if self.input[self.pos..].starts_with("...") {
+            self.pos += 3;
+            true
+        } else {
+            self.expecting(Token::Ellipsis);
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn end(&mut self) -> Result<(), ParseError> {
+        if self.pos == self.input.len() {
+            Ok(())
+        } else {
+            self.expecting(Token::End);
+            Err(self.error())
+        }
+    }
+
+    /// Parses an optionally signed integer, returning the sign and the digits.
+    fn signed_digits(&mut self) -> Result<Option<(bool, &'a str)>, ParseError> {
+        let negative = self.eat('-');
+        let start = self.pos;
+        while let Some('0'..='9') = self.peek() {
+            self.pos += 1;
+        }
+        match &self.input[start..self.pos] {
+            "" if negative => {
+                self.expecting(Token::Integer);
+                Err(self.error())
+            }
+            "" => {
+                self.expecting(Token::Integer);
+                Ok(None)
+            }
+            digits => Ok(Some((negative, digits))),
+        }
+    }
+
+    fn index(&mut self) -> Result<Index, ParseError> {
+        let start = self.pos;
+        let (negative, digits) = match self.signed_digits()? {
+            Some(n) => n,
+            None => return Ok(Index::Default),
+        };
+        let n = digits
+            .parse::<usize>()
+            .map_err(|_| ParseError::new(start, ParseErrorKind::OutOfRange))?;
+        // `-0` is the same as `0`, like in python.
+        Ok(if negative && n != 0 {
+            Index::Tail(n)
+        } else {
+            Index::Head(n)
+        })
+    }
+
+    fn step(&mut self) -> Result<Option<isize>, ParseError> {
+        let start = self.pos;
+        if self.signed_digits()?.is_none() {
+            return Ok(None);
+        }
+        self.input[start..self.pos]
+            .parse::<isize>()
+            .map(Some)
+            .map_err(|_| ParseError::new(start, ParseErrorKind::OutOfRange))
+    }
+
+    /// Parses what `f` parses, optionally within brackets, allowing blanks around it.
+    fn maybe_bracketed<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        self.skip_blanks();
+        let bracketed = self.eat('[');
+        self.skip_blanks();
+        let r = f(self)?;
+        self.skip_blanks();
+        if bracketed {
+            self.expect(']')?;
+            self.skip_blanks();
+        }
+        Ok(r)
+    }
+
+    /// Parses a slice expression, allowing blanks around its parts, omitting the brackets and
+    /// omitting the second colon along with the step.
+    fn slice(&mut self) -> Result<Slice, ParseError> {
+        self.maybe_bracketed(|p| {
+            let start = p.index()?;
+            p.skip_blanks();
+            p.expect(':')?;
+            p.slice_rest(start)
+        })
+    }
+
+    /// Parses the rest of a slice after its start and first colon.
+    fn slice_rest(&mut self, start: Index) -> Result<Slice, ParseError> {
+        self.skip_blanks();
+        let end = self.index()?;
+        self.skip_blanks();
+        let mut step = None;
+        if self.eat(':') {
+            self.skip_blanks();
+            step = self.step()?;
+        }
+        Ok(Slice { start, end, step })
+    }
+
+    fn identifier(&mut self) -> Result<&'a str, ParseError> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c == '_' || c.is_ascii_alphabetic() || self.pos > start && c.is_ascii_digit() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        if self.pos == start {
+            self.expecting(Token::Identifier);
+            return Err(self.error());
+        }
+        Ok(&self.input[start..self.pos])
+    }
+
+    /// Parses a slice, or a single index when there is no colon.
+    #[cfg(feature = "alloc")]
+    fn selector(&mut self) -> Result<Selector, ParseError> {
+        let start = self.index()?;
+        self.skip_blanks();
+        if self.eat(':') {
+            return self.slice_rest(start).map(Selector::Slice);
+        }
+        match start {
+            Index::Default => Err(self.error()),
+            i => Ok(Selector::Index(i)),
+        }
+    }
+
+    /// Parses comma separated selectors, returning a union if there is more than one.
+    #[cfg(feature = "alloc")]
+    fn selectors(&mut self) -> Result<Selector, ParseError> {
+        let first = self.selector()?;
+        self.skip_blanks();
+        if !self.eat(',') {
+            return Ok(first);
+        }
+        let mut union = alloc::vec![first];
+        loop {
+            self.skip_blanks();
+            union.push(self.selector()?);
+            self.skip_blanks();
+            if !self.eat(',') {
+                return Ok(Selector::Union(union));
+            }
+        }
+    }
+
+    /// Skips blank characters: spaces, tabs and line breaks, as allowed by RFC 9535.
+    fn skip_blanks(&mut self) {
+        while let Some(' ') | Some('\t') | Some('\n') | Some('\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    /// Parses an RFC 9535 integer: no leading zeros, no `-0`, within the I-JSON range.
+    fn json_int(&mut self) -> Result<Option<i64>, ParseError> {
+        let start = self.pos;
+        let (negative, digits) = match self.signed_digits()? {
+            Some(n) => n,
+            None => return Ok(None),
+        };
+        if digits.len() > 1 && digits.starts_with('0') || negative && digits == "0" {
+            return Err(ParseError::new(start, ParseErrorKind::InvalidInteger));
+        }
+        match self.input[start..self.pos].parse::<i64>() {
+            Ok(n) if (-rfc9535::MAX_INT..=rfc9535::MAX_INT).contains(&n) => Ok(Some(n)),
+            _ => Err(ParseError::new(start, ParseErrorKind::OutOfRange)),
+        }
+    }
+
+    /// Parses an RFC 9535 slice selector: `[start S] ":" S [end S] [":" [S step]]`.
+    fn rfc9535_selector(&mut self) -> Result<Slice, ParseError> {
+        let start = self.json_int()?;
+        if start.is_some() {
+            self.skip_blanks();
+        }
+        self.expect(':')?;
+        self.skip_blanks();
+        let end = self.json_int()?;
+        if end.is_some() {
+            self.skip_blanks();
+        }
+        let mut step = None;
+        if self.eat(':') {
+            let colon = self.pos;
+            self.skip_blanks();
+            step = self.json_int()?;
+            if step.is_none() {
+                self.pos = colon;
+            }
+        }
+        Ok(rfc9535::new(start, end, step).expect("integers are within the I-JSON range"))
+    }
+
+    /// Parses the selector of one axis: `...`, an integer, or a slice in which the step and its
+    /// colon are optional, as in numpy.
+    #[cfg(feature = "alloc")]
+    fn axis(&mut self) -> Result<AxisSelector, ParseError> {
+        if self.eat_ellipsis() {
+            return Ok(AxisSelector::Ellipsis);
+        }
+        let start = self.index()?;
+        self.skip_blanks();
+        if !self.eat(':') {
+            return match start {
+                Index::Default => Err(self.error()),
+                i => Ok(AxisSelector::Index(i)),
+            };
+        }
+        self.slice_rest(start).map(AxisSelector::Slice)
+    }
+
+    /// Parses comma separated axis selectors within brackets, allowing blanks around them,
+    /// returning each with its offset.
+    #[cfg(feature = "alloc")]
+    fn axes(&mut self) -> Result<Vec<(usize, AxisSelector)>, ParseError> {
+        self.skip_blanks();
+        self.expect('[')?;
+        let mut axes = Vec::new();
+        let mut ellipsis = false;
+        loop {
+            self.skip_blanks();
+            let offset = self.pos;
+            let axis = self.axis()?;
+            if let AxisSelector::Ellipsis = axis {
+                if ellipsis {
+                    // like numpy, allow at most one ellipsis.
+                    return Err(ParseError::new(offset, ParseErrorKind::DuplicateEllipsis));
+                }
+                ellipsis = true;
+            }
+            axes.push((offset, axis));
+            self.skip_blanks();
+            if !self.eat(',') {
+                break;
+            }
+        }
+        self.expect(']')?;
+        self.skip_blanks();
+        Ok(axes)
+    }
+}
+
+impl FromStr for Slice {
+    type Err = ParseError;
+
+    /// Parses a slice expression of the form `[start:end:step]`, where each of the three parts
+    /// is an optional integer.
+    ///
+    /// The parser is lenient about the forms people commonly type: blanks are allowed around the
+    /// parts, the brackets can be omitted, and so can the second colon when there is no step,
+    /// e.g. `1 : -2`, `::2` and `[1:]` are all valid.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut p = Parser::new(s);
+        let slice = p.slice()?;
+        p.end()?;
+        Ok(slice)
+    }
+}
+
+impl FromStr for Step {
+    type Err = ParseError;
+
+    /// Parses the step as it appears in a slice expression: an optional integer, with the empty
+    /// string denoting the default step.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut p = Parser::new(s);
+        let step = p.step()?;
+        p.end()?;
+        Ok(step.into())
+    }
+}
+
+impl TryFrom<&str> for Slice {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl Slice {
+    /// Parses a subscription expression such as `data[1:-1:2]`, returning the identifier and
+    /// the slice.
+    ///
+    /// The identifier is made of ASCII letters, digits and underscores and can't start with a
+    /// digit. The brackets are required, but the slice is otherwise parsed as by `from_str`.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let (name, slice) = Slice::parse_subscript("data[1:-1:2]")?;
+    /// assert_eq!(name, "data");
+    /// assert_eq!(slice.to_string(), "[1:-1:2]");
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn parse_subscript(s: &str) -> Result<(&str, Slice), ParseError> {
+        let mut p = Parser::new(s);
+        p.skip_blanks();
+        let name = p.identifier()?;
+        p.skip_blanks();
+        if p.peek() != Some('[') {
+            p.expecting(Token::OpenBracket);
+            return Err(p.error());
+        }
+        let slice = p.slice()?;
+        p.end()?;
+        Ok((name, slice))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromStr for Selector {
+    type Err = ParseError;
+
+    /// Parses a single index such as `[3]` or a slice expression, with the same leniency as
+    /// `Slice::from_str`, or a comma separated list of them such as `[1:3, 7, -1]`, which is
+    /// parsed into a union in the same order.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::{Index, Selector};
+    /// assert!(matches!("[-1]".parse()?, Selector::Index(Index::Tail(1))));
+    /// assert!(matches!("[1:]".parse()?, Selector::Slice(_)));
+    /// let s: Selector = "[1:3, 7, -1]".parse()?;
+    /// assert_eq!(s.indices(10), vec![1, 2, 7, 9]);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut p = Parser::new(s);
+        let selector = p.maybe_bracketed(Parser::selectors)?;
+        p.end()?;
+        Ok(selector)
+    }
+}
+
+/// Parses an RFC 9535 slice selector, such as `1:5:2`.
+pub(crate) fn parse_rfc9535(s: &str) -> Result<Slice, ParseError> {
+    let mut p = Parser::new(s);
+    let slice = p.rfc9535_selector()?;
+    p.end()?;
+    Ok(slice)
+}
+
+#[cfg(feature = "alloc")]
+impl AxisSelector {
+    /// Parses a numpy-style multi-axis expression such as `[1:3, ..., -1]`: comma separated
+    /// slices (whose step is optional), integers and at most one ellipsis.
+    pub fn parse_list(s: &str) -> Result<Vec<AxisSelector>, ParseError> {
+        let mut p = Parser::new(s);
+        let axes = p.axes()?;
+        p.end()?;
+        Ok(axes.into_iter().map(|(_, axis)| axis).collect())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromStr for MultiSlice {
+    type Err = ParseError;
+
+    /// Parses a numpy-style multi-axis expression made only of slices, optionally followed by
+    /// an ellipsis, such as `[::2, 1:]`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut p = Parser::new(s);
+        let axes = p.axes()?;
+        p.end()?;
+        let last = axes.len() - 1;
+        axes.into_iter()
+            .enumerate()
+            .filter_map(|(n, (offset, axis))| match axis {
+                AxisSelector::Slice(s) => Some(Ok(s)),
+                AxisSelector::Ellipsis if n == last => None,
+                _ => Some(Err(ParseError::new(offset, ParseErrorKind::NotASlice))),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(MultiSlice::new)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() -> Result<(), ParseError> {
+        let s: Slice = "[:-2:1]".parse()?;
+        assert_eq!(s.start, Index::Default);
+        assert_eq!(s.end, Index::Tail(2));
+        assert_eq!(s.step, Some(1));
+
+        let s: Slice = "[1:-0:-3]".parse()?;
+        assert_eq!(s.start, Index::Head(1));
+        assert_eq!(s.end, Index::Head(0));
+        assert_eq!(s.step, Some(-3));
+
+        let s: Slice = "[::]".parse()?;
+        assert_eq!(s.start, Index::Default);
+        assert_eq!(s.end, Index::Default);
+        assert_eq!(s.step, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_lenient() -> Result<(), ParseError> {
+        let canonical = |s: &str| s.parse::<Slice>().map(|s| s.to_string());
+
+        assert_eq!(canonical("[ 1 : -2 : 2 ]")?, "[1:-2:2]");
+        assert_eq!(canonical("1 : -2 : 2")?, "[1:-2:2]");
+        assert_eq!(canonical("\t::-1\n")?, "[::-1]");
+        assert_eq!(canonical("[1:2]")?, "[1:2:]");
+        assert_eq!(canonical("1:2")?, "[1:2:]");
+        assert_eq!(canonical("-5:-1")?, "[-5:-1:]");
+        assert_eq!(canonical("-3:")?, "[-3::]");
+        assert_eq!(canonical(":")?, "[::]");
+        assert_eq!(canonical(" [:] ")?, "[::]");
+        Ok(())
+    }
+
+    #[test]
+    fn parse_errors() {
+        let offset = |s: &str| s.parse::<Slice>().unwrap_err().offset();
+
+        assert_eq!(offset(""), 0);
+        assert_eq!(offset("[]"), 1);
+        assert_eq!(offset("[1]"), 2);
+        assert_eq!(offset("[1:2"), 4);
+        assert_eq!(offset("1:2]"), 3);
+        assert_eq!(offset("[1 2:3]"), 3);
+        assert_eq!(offset("[- 1:2]"), 2);
+        assert_eq!(offset("[1:2:3:]"), 6);
+        assert_eq!(offset("[-:2:3]"), 2);
+        assert_eq!(offset("[1:2:3]x"), 7);
+        assert_eq!(offset("[1:2:99999999999999999999]"), 5);
+    }
+
+    #[test]
+    fn parse_error_diagnostics() {
+        let err = |s: &str| {
+            let e = s.parse::<Slice>().unwrap_err();
+            (e.kind(), e.expected().collect::<Vec<_>>(), e.to_string())
+        };
+        use ParseErrorKind::*;
+        use Token::*;
+
+        assert_eq!(
+            err("[1:"),
+            (
+                Unexpected,
+                vec![CloseBracket, Colon, Integer],
+                "invalid slice expression at offset 3: expected ']', ':' or an integer".to_string()
+            )
+        );
+        assert_eq!(err("[1:2").1, vec![CloseBracket, Colon]);
+        assert_eq!(err("1:2]").1, vec![Colon, End]);
+        assert_eq!(err("").1, vec![OpenBracket, Colon, Integer]);
+        assert_eq!(err("[-x::]").1, vec![Integer]);
+        assert_eq!(
+            err("[99999999999999999999::]"),
+            (
+                OutOfRange,
+                vec![],
+                "invalid slice expression at offset 1: integer out of range".to_string()
+            )
+        );
+
+        let rfc = |s: &str| crate::rfc9535::parse(s).unwrap_err().kind();
+        assert_eq!(rfc("01:"), InvalidInteger);
+        assert_eq!(rfc("9007199254740992:"), OutOfRange);
+    }
+
+    #[test]
+    fn parse_subscript() {
+        let (name, s) = Slice::parse_subscript(" _x1 [ ::-1 ] ").unwrap();
+        assert_eq!((name, s.to_string().as_str()), ("_x1", "[::-1]"));
+        let (name, s) = Slice::parse_subscript("rows[2:]").unwrap();
+        assert_eq!((name, s.to_string().as_str()), ("rows", "[2::]"));
+
+        let err = |s: &str| Slice::parse_subscript(s).unwrap_err();
+        assert_eq!(err("1x[::]").expected().next(), Some(Token::Identifier));
+        assert_eq!(err("x::").offset(), 1);
+        assert_eq!(err("x").expected().next(), Some(Token::OpenBracket));
+        assert_eq!(err("x[::").offset(), 4);
+        assert_eq!(err("x[::]y").offset(), 5);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn parse_selector() {
+        let parse = |s: &str| s.parse::<Selector>();
+
+        assert!(matches!(parse("[3]"), Ok(Selector::Index(Index::Head(3)))));
+        assert!(matches!(
+            parse(" [ -1 ] "),
+            Ok(Selector::Index(Index::Tail(1)))
+        ));
+        assert!(matches!(parse("0"), Ok(Selector::Index(Index::Head(0)))));
+        assert!(matches!(parse("[3:]"), Ok(Selector::Slice(s)) if s.to_string() == "[3::]"));
+        assert!(matches!(parse("::-1"), Ok(Selector::Slice(s)) if s.to_string() == "[::-1]"));
+
+        let err = |s: &str| parse(s).unwrap_err();
+        assert_eq!(err("[]").offset(), 1);
+        assert_eq!(
+            err("[3").expected().collect::<Vec<_>>(),
+            [Token::CloseBracket, Token::Colon, Token::Comma]
+        );
+        assert_eq!(err("[3]]").offset(), 3);
+        assert_eq!(err("[1,]").offset(), 3);
+        assert_eq!(err("[1,,2]").offset(), 3);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn parse_union() {
+        let indices = |s: &str| s.parse::<Selector>().unwrap().indices(10);
+
+        assert_eq!(indices("[1:3, 7, -1]"), [1, 2, 7, 9]);
+        assert_eq!(indices("[-1,-1]"), [9, 9]);
+        assert_eq!(indices("::-4, 0"), [9, 5, 1, 0]);
+        assert_eq!(indices("[ 2 ,\t::3 ]"), [2, 0, 3, 6, 9]);
+        assert!(matches!(
+            "[1, 2]".parse::<Selector>(),
+            Ok(Selector::Union(u)) if u.len() == 2
+        ));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn parse_axes() {
+        use AxisSelector::*;
+
+        let axes = AxisSelector::parse_list("[1:3, ..., -1,::2]").unwrap();
+        assert_eq!(axes.len(), 4);
+        assert!(matches!(&axes[0], Slice(s) if s.to_string() == "[1:3:]"));
+        assert!(matches!(axes[1], Ellipsis));
+        assert!(matches!(axes[2], Index(crate::Index::Tail(1))));
+        assert!(matches!(&axes[3], Slice(s) if s.to_string() == "[::2]"));
+
+        let err = |s: &str| AxisSelector::parse_list(s).unwrap_err().offset();
+        assert_eq!(err("[..., 1, ...]"), 9);
+        assert_eq!(
+            AxisSelector::parse_list("[..., 1, ...]")
+                .unwrap_err()
+                .kind(),
+            ParseErrorKind::DuplicateEllipsis
+        );
+        assert_eq!(err("[1,,2]"), 3);
+        assert_eq!(err("[1:2"), 4);
+
+        // blanks are allowed wherever `Slice::from_str` allows them.
+        for expr in &["[1 : 3, 2]", " [1:3:,\t2:4]\n", "[ 1 :3 : 2 ,...]"] {
+            assert!(AxisSelector::parse_list(expr).is_ok(), "{:?}", expr);
+        }
+        let axes = AxisSelector::parse_list("[1 : 3 : 2 , -1]").unwrap();
+        assert!(matches!(&axes[0], Slice(s) if s.to_string() == "[1:3:2]"));
+        assert!(matches!(axes[1], Index(crate::Index::Tail(1))));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn parse_multi_slice() {
+        let m: MultiSlice = "[::2, 1:, ...]".parse().unwrap();
+        assert_eq!(m.axes.len(), 2);
+        assert_eq!(m.axes[1].to_string(), "[1::]");
+
+        let err = |s: &str| s.parse::<MultiSlice>().unwrap_err().offset();
+        assert_eq!(err("[::2, 1]"), 6);
+        assert_eq!(
+            "[::2, 1]".parse::<MultiSlice>().unwrap_err().kind(),
+            ParseErrorKind::NotASlice
+        );
+        assert_eq!(err("[..., ::2]"), 1);
+        assert!("[1 : 3, 2 :]".parse::<MultiSlice>().is_ok());
+    }
+}
@@ -0,0 +1,68 @@
+//! Slicing of `im::Vector`s, the persistent vectors of the `im` crate.
+
+use crate::Slice;
+use im::Vector;
+
+impl Slice {
+    /// Returns an iterator that yields the elements of an `im::Vector` that match the slice
+    /// expression.
+    pub fn apply_im<'a, T: Clone>(&self, v: &'a Vector<T>) -> impl Iterator<Item = &'a T> + 'a {
+        self.indices(v.len()).map(move |i| &v[i])
+    }
+
+    /// Returns a new `im::Vector` with the elements of `v` that match the slice expression.
+    ///
+    /// When the selection is contiguous and in order (a step of 1, or at most one selected
+    /// element) the result shares its structure with `v` and is built in logarithmic time,
+    /// without cloning any element. Otherwise the selected elements are cloned into a new
+    /// vector.
+    ///
+    /// # Example
+    /// ```
+    /// use im::Vector;
+    /// use slyce::Slice;
+    /// let history: Vector<i32> = (0..1000).collect();
+    ///
+    /// let s: Slice = "[-3:]".parse()?;
+    /// assert_eq!(s.apply_im_vector(&history), Vector::from(vec![997, 998, 999]));
+    ///
+    /// let s: Slice = "[-1:-6:-2]".parse()?;
+    /// assert_eq!(s.apply_im_vector(&history), Vector::from(vec![999, 997, 995]));
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn apply_im_vector<T: Clone>(&self, v: &Vector<T>) -> Vector<T> {
+        let it = self.indices(v.len());
+        let len = it.len();
+        if len == 0 {
+            return Vector::new();
+        }
+        if len == 1 || (it.forward && it.stride == 1) {
+            return v.skip(it.next).take(len);
+        }
+        it.map(|i| v[i].clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn apply_im() {
+        // long enough to span several chunks of the vector.
+        let v: Vector<i32> = (0..200).collect();
+        for &(expr, ref want) in &[
+            ("[60:70:]", (60..70).collect::<Vec<_>>()),
+            ("[-3:]", vec![197, 198, 199]),
+            ("[::50]", vec![0, 50, 100, 150]),
+            ("[130:120:-3]", vec![130, 127, 124, 121]),
+            ("[5:6:]", vec![5]),
+            ("[300::]", vec![]),
+            ("[::0]", vec![]),
+        ] {
+            let s: Slice = expr.parse().unwrap();
+            assert_eq!(s.apply_im(&v).copied().collect::<Vec<_>>(), *want, "{}", s);
+            assert_eq!(s.apply_im_vector(&v), Vector::from(want.clone()), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Conversions between `Slice` and python `slice` objects.
+
+use crate::{Index, Slice};
+use core::convert::TryFrom;
+use pyo3::exceptions::PyOverflowError;
+use pyo3::prelude::*;
+use pyo3::types::PySlice;
+
+/// Returns the python integer for the index; `Tail(0)` has no python spelling, as `-0` is the
+/// same as `0`, so it's converted to `usize::MAX` like `Slice`'s `Display` does.
+fn index_to_py(py: Python<'_>, i: &Index) -> PyObject {
+    match *i {
+        Index::Tail(0) => usize::MAX.into_py(py),
+        Index::Head(n) => n.into_py(py),
+        Index::Tail(n) => (-(n as i128)).into_py(py),
+        Index::Default => py.None(),
+    }
+}
+
+fn index_from_py(obj: &PyAny) -> PyResult<Index> {
+    let i = match obj.extract::<Option<i128>>()? {
+        Some(i) => i,
+        None => return Ok(Index::Default),
+    };
+    Index::try_from(i).map_err(|_| PyOverflowError::new_err("slice index out of range"))
+}
+
+impl ToPyObject for Slice {
+    /// Returns the python `slice` equivalent to this slice; default indices and steps map to
+    /// `None`.
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let args = (
+            index_to_py(py, &self.start),
+            index_to_py(py, &self.end),
+            self.step,
+        );
+        py.get_type::<PySlice>()
+            .call1(args)
+            .expect("constructing a slice from integers and None cannot fail")
+            .into()
+    }
+}
+
+impl IntoPy<PyObject> for Slice {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl<'source> FromPyObject<'source> for Slice {
+    /// Extracts a slice from a python `slice` whose fields are integers or `None`.
+    fn extract(obj: &'source PyAny) -> PyResult<Self> {
+        let slice: &PySlice = obj.downcast()?;
+        Ok(Slice {
+            start: index_from_py(slice.getattr("start")?)?,
+            end: index_from_py(slice.getattr("stop")?)?,
+            step: slice.getattr("step")?.extract()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn with_gil<F: FnOnce(Python<'_>)>(f: F) {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(f)
+    }
+
+    #[test]
+    fn round_trip() {
+        with_gil(|py| {
+            for expr in &["[::]", "[1:-2:2]", "[-1::-1]", "[:0:]"] {
+                let s: Slice = expr.parse().unwrap();
+                let obj = s.to_object(py);
+                assert!(obj.as_ref(py).is_instance_of::<PySlice>());
+                assert_eq!(obj.extract::<Slice>(py).unwrap().to_string(), s.to_string());
+            }
+
+            // the end of the array, which python can't spell as -0.
+            let s = Slice::new(Index::Tail(2), Index::Tail(0), None);
+            let list: &PyAny = pyo3::types::PyList::new(py, [1, 2, 3]);
+            let v: Vec<i32> = list.get_item(s.to_object(py)).unwrap().extract().unwrap();
+            assert_eq!(v, [2, 3]);
+        });
+    }
+
+    #[test]
+    fn from_python() {
+        with_gil(|py| {
+            let obj = py.eval("slice(None, -3, 2)", None, None).unwrap();
+            let s: Slice = obj.extract().unwrap();
+            assert_eq!(s.start, Index::Default);
+            assert_eq!(s.end, Index::Tail(3));
+            assert_eq!(s.step, Some(2));
+
+            let v: Vec<usize> = py
+                .eval("list(range(10))[slice(8, 1, -3)]", None, None)
+                .unwrap()
+                .extract()
+                .unwrap();
+            let s: Slice = py
+                .eval("slice(8, 1, -3)", None, None)
+                .unwrap()
+                .extract()
+                .unwrap();
+            let want: Vec<usize> = s.apply(&(0..10).collect::<Vec<_>>()).copied().collect();
+            assert_eq!(v, want);
+        });
+    }
+
+    /// Compares `apply` with the slicing of python lists, in process.
+    #[test]
+    fn differential() {
+        with_gil(|py| {
+            // python rejects a zero step, while slyce returns an empty slice.
+            for (len, s) in crate::differential_cases().filter(|(_, s)| s.step != Some(0)) {
+                let v: Vec<usize> = (0..len).collect();
+                let list: &PyAny = pyo3::types::PyList::new(py, &v);
+                let want: Vec<usize> = list
+                    .get_item(s.to_object(py))
+                    .and_then(|r| r.extract())
+                    .unwrap();
+                let got: Vec<usize> = s.apply(&v).copied().collect();
+                assert_eq!(got, want, "{} on {:?}", s, v);
+            }
+        });
+    }
+
+    #[test]
+    fn from_python_errors() {
+        with_gil(|py| {
+            for expr in &[
+                "1",
+                "slice('a', None)",
+                "slice(2**200)",
+                "slice(None, None, 1.5)",
+            ] {
+                let obj = py.eval(expr, None, None).unwrap();
+                assert!(obj.extract::<Slice>().is_err(), "{}", expr);
+            }
+        });
+    }
+}
@@ -0,0 +1,113 @@
+//! `quickcheck` support for indices and slices.
+
+use crate::{Index, Slice};
+use alloc::boxed::Box;
+use quickcheck::{Arbitrary, Gen};
+
+/// Returns a magnitude that is usually smaller than the size of the generator, but occasionally
+/// of any value.
+fn magnitude(g: &mut Gen) -> usize {
+    let n = usize::arbitrary(g);
+    if *g.choose(&[true, true, true, false]).unwrap() {
+        n % (g.size() + 1)
+    } else {
+        n
+    }
+}
+
+impl Arbitrary for Index {
+    /// Generates `Default`, `Head(n)` or `Tail(n)` with `n` usually small; `Tail(0)` is never
+    /// generated.
+    fn arbitrary(g: &mut Gen) -> Self {
+        match g.choose(&[0, 1, 2]).unwrap() {
+            0 => Index::Default,
+            1 => Index::Head(magnitude(g)),
+            _ => Index::Tail(magnitude(g).max(1)),
+        }
+    }
+
+    /// Shrinks towards `Default`, then towards `Head(0)`.
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        match *self {
+            Index::Default => quickcheck::empty_shrinker(),
+            Index::Head(n) => {
+                Box::new(core::iter::once(Index::Default).chain(n.shrink().map(Index::Head)))
+            }
+            Index::Tail(n) => Box::new(
+                [Index::Default, Index::Head(0)]
+                    .to_vec()
+                    .into_iter()
+                    .chain(n.shrink().filter(|&n| n > 0).map(Index::Tail)),
+            ),
+        }
+    }
+}
+
+impl Arbitrary for Slice {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let step = match g.choose(&[0, 1, 2, 3]).unwrap() {
+            0 => None,
+            1 => Some(isize::arbitrary(g)),
+            _ => Some((magnitude(g) % 5) as isize * *g.choose(&[1, -1]).unwrap()),
+        };
+        Slice {
+            start: Index::arbitrary(g),
+            end: Index::arbitrary(g),
+            step,
+        }
+    }
+
+    /// Shrinks one component at a time, moving indices towards `Default` and the step towards
+    /// `None`, then towards zero.
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let (start, end, step) = (self.start.clone(), self.end.clone(), self.step);
+        let this = self.clone();
+        let starts = self.start.shrink().map(move |start| Slice {
+            start,
+            ..this.clone()
+        });
+        let this = self.clone();
+        let ends = self.end.shrink().map(move |end| Slice {
+            end,
+            ..this.clone()
+        });
+        let steps = step.shrink().map(move |step| Slice {
+            start: start.clone(),
+            end: end.clone(),
+            step,
+        });
+        Box::new(starts.chain(ends).chain(steps))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::oracle;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn apply_matches_oracle() {
+        fn prop(s: Slice, len: u8) -> bool {
+            let v: Vec<u8> = (0..len).collect();
+            s.start != Index::Tail(0)
+                && s.end != Index::Tail(0)
+                && s.apply(&v).collect::<Vec<_>>() == oracle::apply(&s, &v)
+        }
+        quickcheck::quickcheck(prop as fn(Slice, u8) -> bool);
+    }
+
+    #[test]
+    fn shrink() {
+        let shrunk: Vec<Index> = Index::Tail(4).shrink().collect();
+        assert_eq!(shrunk[..2], [Index::Default, Index::Head(0)]);
+        assert!(shrunk[2..]
+            .iter()
+            .all(|i| matches!(i, Index::Tail(n) if (1..4).contains(n))));
+
+        let s = Slice::new(Index::Head(3), Index::Default, Some(2));
+        let first = s.shrink().next().unwrap();
+        assert_eq!(first.start, Index::Default);
+        assert!(s.shrink().any(|s| s.step.is_none()));
+    }
+}
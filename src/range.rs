@@ -0,0 +1,93 @@
+//! Slicing of ranges of integers treated as arrays, without materializing them.
+
+use crate::{Index, Slice};
+use core::ops::Range;
+
+impl Slice {
+    /// Returns an iterator that yields the values of `range` selected by the slice, as if
+    /// `range` were an array holding them.
+    ///
+    /// The range is never materialized and all the arithmetic uses 64 bits, so this works for
+    /// sequence numbers and file offsets even on targets with a 32-bit `usize`.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let s: Slice = "[-3::-2]".parse()?;
+    /// let offsets: Vec<u64> = s.apply_range(1 << 40..(1 << 40) + 10).collect();
+    /// assert_eq!(offsets, vec![(1 << 40) + 7, (1 << 40) + 5, (1 << 40) + 3, (1 << 40) + 1]);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn apply_range(&self, range: Range<u64>) -> impl Iterator<Item = u64> {
+        let len = range.end.saturating_sub(range.start);
+        let step = self.step_or_default();
+        let forward = step >= 0;
+        let (def_start, def_end) = if forward { (0, len) } else { (len, 0) };
+        let start = bound(&self.start, len, forward).unwrap_or(def_start);
+        let end = bound(&self.end, len, forward).unwrap_or(def_end);
+        let stride = step.unsigned_abs() as u64;
+
+        let remaining = match (stride, forward) {
+            (0, _) => 0,
+            (_, true) if start < end => (end - start - 1) / stride + 1,
+            (_, false) if start > end => (start - end - 1) / stride + 1,
+            _ => 0,
+        };
+        // a backward bound is the position past the element it denotes.
+        let first = range.start
+            + if forward {
+                start
+            } else {
+                start.saturating_sub(1)
+            };
+        (0..remaining).map(move |k| {
+            // k < remaining, so the offset stays within the range.
+            if forward {
+                first + k * stride
+            } else {
+                first - k * stride
+            }
+        })
+    }
+}
+
+/// Like `Index::to_bound`, for a 64-bit length.
+fn bound(i: &Index, len: u64, forward: bool) -> Option<u64> {
+    match (i, forward) {
+        (&Index::Head(n), true) => Some((n as u64).min(len)),
+        (&Index::Head(n), false) => Some((n as u64).saturating_add(1).min(len)),
+        (&Index::Tail(n), true) => Some(len.saturating_sub(n as u64)),
+        (&Index::Tail(n), false) => Some(len.saturating_sub((n as u64).saturating_sub(1))),
+        (Index::Default, _) => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn apply_range() {
+        let sel = |expr: &str, r| {
+            expr.parse::<Slice>()
+                .unwrap()
+                .apply_range(r)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sel("[::]", 100..104), [100, 101, 102, 103]);
+        assert_eq!(sel("[1:-1:]", 100..104), [101, 102]);
+        assert_eq!(sel("[::-1]", 100..104), [103, 102, 101, 100]);
+        assert_eq!(sel("[1::2]", 100..105), [101, 103]);
+        assert_eq!(sel("[-1:0:-3]", 100..108), [107, 104, 101]);
+        assert_eq!(sel("[10::]", 100..104), [0; 0]);
+        assert_eq!(sel("[::0]", 100..104), [0; 0]);
+
+        let s: Slice = "[-2::]".parse().unwrap();
+        let r: Vec<u64> = s.apply_range(0..u64::MAX).collect();
+        assert_eq!(r, vec![u64::MAX - 2, u64::MAX - 1]);
+        let s: Slice = "[::-1]".parse().unwrap();
+        assert_eq!(s.apply_range(5..u64::MAX).next(), Some(u64::MAX - 1));
+        let (start, end) = (5, 3);
+        assert_eq!(s.apply_range(start..end).count(), 0);
+    }
+}
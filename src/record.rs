@@ -0,0 +1,45 @@
+//! Slicing of the fields of `csv` records.
+
+use crate::Slice;
+use csv::{ByteRecord, StringRecord};
+
+impl Slice {
+    /// Returns a record with the fields of `record` that match the slice expression, i.e. the
+    /// selected columns.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let record = csv::StringRecord::from(vec!["id", "name", "age", "city"]);
+    /// let s: Slice = "[1:-1]".parse()?;
+    /// assert_eq!(s.apply_record(&record), vec!["name", "age"]);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn apply_record(&self, record: &StringRecord) -> StringRecord {
+        self.apply_fn(record.len(), |i| &record[i]).collect()
+    }
+
+    /// Returns a record with the fields of `record` that match the slice expression, i.e. the
+    /// selected columns, without requiring them to be valid UTF-8.
+    pub fn apply_byte_record(&self, record: &ByteRecord) -> ByteRecord {
+        self.apply_fn(record.len(), |i| &record[i]).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn apply_record() {
+        let fields = vec!["a", "b", "c", "d", "e"];
+        let record = StringRecord::from(fields.clone());
+        let bytes = ByteRecord::from(fields.clone());
+        for expr in &["[::]", "[1:-1]", "[::-2]", "[9:]"] {
+            let s: Slice = expr.parse().unwrap();
+            let want: Vec<&str> = s.apply(&fields).copied().collect();
+            assert_eq!(s.apply_record(&record), want, "{}", expr);
+            assert_eq!(s.apply_byte_record(&bytes), want, "{}", expr);
+        }
+    }
+}
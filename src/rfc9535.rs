@@ -0,0 +1,156 @@
+//! Compliance with the array slice selector of JSONPath, as specified in
+//! [RFC 9535, section 2.3.4](https://www.rfc-editor.org/rfc/rfc9535#name-array-slice-selector).
+//!
+//! A `Slice` built by this module selects exactly the elements, in the same order, that the
+//! RFC's slice selector selects from an array of the same length. In particular:
+//!
+//! * a step of zero selects nothing, instead of being an error;
+//! * indices outside of the array are clamped to its bounds rather than being errors;
+//! * the defaults of `start` and `end` depend on the sign of the step.
+//!
+//! These are also the semantics of `Slice::apply` in general; what this module adds is the RFC
+//! grammar, which is stricter than the python-like one accepted by `Slice::from_str`, and its
+//! restriction of integers to the I-JSON range.
+//!
+//! # Example
+//! ```
+//! use slyce::rfc9535;
+//! let arr = ["a", "b", "c", "d", "e", "f", "g"];
+//! let s = rfc9535::parse("5:1:-2")?;
+//! assert_eq!(s.apply(&arr).collect::<Vec<_>>(), vec![&"f", &"d"]);
+//! # Ok::<(), slyce::ParseError>(())
+//! ```
+
+use crate::{Index, ParseError, Slice};
+use core::convert::TryFrom;
+
+/// The largest integer allowed by RFC 9535, `2^53 - 1`; the smallest is its opposite.
+pub const MAX_INT: i64 = (1 << 53) - 1;
+
+fn index(i: Option<i64>) -> Index {
+    // indices that don't fit in a usize are past the end of any array, like usize::MAX.
+    let magnitude = |n: u64| usize::try_from(n).unwrap_or(usize::MAX);
+    match i {
+        None => Index::Default,
+        Some(n) if n < 0 => Index::Tail(magnitude(n.unsigned_abs())),
+        Some(n) => Index::Head(magnitude(n as u64)),
+    }
+}
+
+/// Returns the slice equivalent to the RFC 9535 selector `start:end:step`, where omitted parts
+/// are `None`.
+///
+/// Returns `None` if any of the integers is outside of the I-JSON range `[-MAX_INT, MAX_INT]`.
+pub fn new(start: Option<i64>, end: Option<i64>, step: Option<i64>) -> Option<Slice> {
+    let valid = |i: Option<i64>| i.map_or(true, |n| (-MAX_INT..=MAX_INT).contains(&n));
+    if !(valid(start) && valid(end) && valid(step)) {
+        return None;
+    }
+    // steps that don't fit in an isize are larger than any array, like isize::MAX.
+    let step =
+        step.map(|n| isize::try_from(n).unwrap_or(if n < 0 { -isize::MAX } else { isize::MAX }));
+    Some(Slice {
+        start: index(start),
+        end: index(end),
+        step,
+    })
+}
+
+/// Parses an RFC 9535 slice selector, such as `1:5:2` or `::-1`.
+///
+/// The selector has no surrounding brackets; blanks are allowed around the colons as in the
+/// RFC grammar, and integers can't have leading zeros, be `-0` or exceed the I-JSON range.
+pub fn parse(selector: &str) -> Result<Slice, ParseError> {
+    crate::parse::parse_rfc9535(selector)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// The normative algorithm of section 2.3.4.2.2, transcribed literally.
+    fn rfc_indices(start: Option<i64>, end: Option<i64>, step: Option<i64>, len: i64) -> Vec<i64> {
+        let step = step.unwrap_or(1);
+        let (start, end) = if step >= 0 {
+            (start.unwrap_or(0), end.unwrap_or(len))
+        } else {
+            (start.unwrap_or(len - 1), end.unwrap_or(-len - 1))
+        };
+        let normalize = |i: i64| if i >= 0 { i } else { len + i };
+        let (n_start, n_end) = (normalize(start), normalize(end));
+        let mut r = Vec::new();
+        if step > 0 {
+            let lower = n_start.max(0).min(len);
+            let upper = n_end.max(0).min(len);
+            let mut i = lower;
+            while i < upper {
+                r.push(i);
+                i += step;
+            }
+        } else if step < 0 {
+            let upper = n_start.max(-1).min(len - 1);
+            let lower = n_end.max(-1).min(len - 1);
+            let mut i = upper;
+            while lower < i {
+                r.push(i);
+                i += step;
+            }
+        }
+        r
+    }
+
+    #[test]
+    fn rfc_examples() {
+        let arr = ["a", "b", "c", "d", "e", "f", "g"];
+        let sel = |s: &str| parse(s).unwrap().apply(&arr).copied().collect::<Vec<_>>();
+        assert_eq!(sel("1:3"), ["b", "c"]);
+        assert_eq!(sel("5:"), ["f", "g"]);
+        assert_eq!(sel("1:5:2"), ["b", "d"]);
+        assert_eq!(sel("5:1:-2"), ["f", "d"]);
+        assert_eq!(sel("::-1"), ["g", "f", "e", "d", "c", "b", "a"]);
+    }
+
+    #[test]
+    fn matches_rfc_algorithm() {
+        let ints = [
+            -MAX_INT, -9, -8, -5, -3, -2, -1, 0, 1, 2, 3, 5, 8, 9, MAX_INT,
+        ];
+        let opts = || ints.iter().copied().map(Some).chain(Some(None));
+        for len in 0..8 {
+            let v: Vec<i64> = (0..len).collect();
+            for start in opts() {
+                for end in opts() {
+                    for step in opts() {
+                        let s = new(start, end, step).unwrap();
+                        let got: Vec<i64> = s.apply(&v).copied().collect();
+                        let want = rfc_indices(start, end, step, len);
+                        assert_eq!(got, want, "{:?}:{:?}:{:?} on {}", start, end, step, len);
+                    }
+                }
+            }
+        }
+        assert_eq!(rfc_indices(Some(0), None, Some(0), 3), [0i64; 0]);
+    }
+
+    #[test]
+    fn grammar() {
+        let ok = |s: &str| parse(s).unwrap().to_string();
+        assert_eq!(ok(":"), "[::]");
+        assert_eq!(ok("::"), "[::]");
+        assert_eq!(ok("1 :\t-2 :\n 3"), "[1:-2:3]");
+        assert_eq!(ok("0:0:0"), "[0:0:0]");
+        assert_eq!(ok("-9007199254740991::"), "[-9007199254740991::]");
+
+        let err = |s: &str| parse(s).unwrap_err().offset();
+        assert_eq!(err(""), 0);
+        assert_eq!(err("1"), 1);
+        assert_eq!(err(" 1:2"), 0);
+        assert_eq!(err("[1:2]"), 0);
+        assert_eq!(err("01:2"), 0);
+        assert_eq!(err("-0:2"), 0);
+        assert_eq!(err("1:2: "), 4);
+        assert_eq!(err("1:2:3:"), 5);
+        assert_eq!(err("9007199254740992:"), 0);
+        assert!(new(None, None, Some(MAX_INT + 1)).is_none());
+    }
+}
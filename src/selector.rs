@@ -0,0 +1,110 @@
+//! Selectors that combine slices with single indices, wildcards and unions, in the style of
+//! JSONPath and jq.
+
+use crate::{Index, Slice};
+use alloc::vec::Vec;
+
+/// Selects elements of an array by position.
+///
+/// # Example
+/// ```
+/// use slyce::{Index, Selector, Slice};
+/// let s = Selector::Union(vec![
+///     Selector::Index(Index::Tail(1)),
+///     Selector::Slice("[:2:]".parse()?),
+///     Selector::Index(Index::Head(0)),
+/// ]);
+/// assert_eq!(s.apply(&[10, 20, 30]), vec![&30, &10, &20, &10]);
+/// # Ok::<(), slyce::ParseError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub enum Selector {
+    /// The single element at a position, if it's within the array. `Index::Default` selects
+    /// nothing.
+    Index(Index),
+    /// The elements selected by a slice.
+    Slice(Slice),
+    /// All the elements, in order.
+    Wildcard,
+    /// The elements selected by each selector in turn; an element selected more than once is
+    /// repeated.
+    Union(Vec<Selector>),
+}
+
+impl Selector {
+    /// Returns the positions selected in an array of length `len`, in selection order.
+    pub fn indices(&self, len: usize) -> Vec<usize> {
+        let mut r = Vec::new();
+        self.push_indices(len, &mut r);
+        r
+    }
+
+    fn push_indices(&self, len: usize, r: &mut Vec<usize>) {
+        match self {
+            Selector::Index(i) => r.extend(i.resolve(len)),
+            Selector::Slice(s) => r.extend(s.indices(len)),
+            Selector::Wildcard => r.extend(0..len),
+            Selector::Union(selectors) => {
+                for s in selectors {
+                    s.push_indices(len, r);
+                }
+            }
+        }
+    }
+
+    /// Returns references to the selected elements of `arr`, in selection order.
+    pub fn apply<'a, T>(&self, arr: &'a [T]) -> Vec<&'a T> {
+        self.indices(arr.len())
+            .into_iter()
+            .map(|i| &arr[i])
+            .collect()
+    }
+}
+
+impl From<Index> for Selector {
+    fn from(i: Index) -> Self {
+        Selector::Index(i)
+    }
+}
+
+impl From<Slice> for Selector {
+    fn from(s: Slice) -> Self {
+        Selector::Slice(s)
+    }
+}
+
+impl From<Vec<Selector>> for Selector {
+    fn from(selectors: Vec<Selector>) -> Self {
+        Selector::Union(selectors)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn index() {
+        let v = [0, 1, 2];
+        let sel = |i: Index| Selector::Index(i).apply(&v);
+        assert_eq!(sel(Index::Head(0)), [&0]);
+        assert_eq!(sel(Index::Head(3)), Vec::<&i32>::new());
+        assert_eq!(sel(Index::Tail(3)), [&0]);
+        assert_eq!(sel(Index::Tail(4)), Vec::<&i32>::new());
+        assert_eq!(sel(Index::Tail(0)), Vec::<&i32>::new());
+        assert_eq!(sel(Index::Default), Vec::<&i32>::new());
+    }
+
+    #[test]
+    fn union() {
+        let v = [0, 1, 2, 3];
+        let s: Selector = vec![
+            Selector::Wildcard,
+            Selector::Slice("[::-2]".parse().unwrap()),
+            vec![Index::Head(1).into(), Index::Head(1).into()].into(),
+        ]
+        .into();
+        assert_eq!(s.indices(v.len()), [0, 1, 2, 3, 3, 1, 1, 1]);
+        assert_eq!(Selector::Union(vec![]).apply(&v), Vec::<&i32>::new());
+    }
+}
@@ -0,0 +1,155 @@
+//! Slicing of shared slices, such as `Arc<[T]>` and `Rc<[T]>`, into views sharing ownership.
+
+use crate::Slice;
+use alloc::vec::Vec;
+use core::ops::{Deref, Range};
+
+impl Slice {
+    /// Returns a view of the elements of `owner` that match the slice expression, sharing
+    /// ownership of `owner` instead of copying the elements.
+    ///
+    /// This works for any cheaply cloneable pointer to a slice, such as `Arc<[T]>` and
+    /// `Rc<[T]>`. When the selection is contiguous and in order (a step of 1, or at most one
+    /// selected element) the view only clones `owner`, which doesn't allocate. Otherwise the
+    /// selected elements are cloned into a new owner.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// use std::sync::Arc;
+    /// let data: Arc<[i32]> = Arc::from(vec![1, 2, 3, 4, 5]);
+    ///
+    /// let s: Slice = "[1:-1:]".parse()?;
+    /// let view = s.apply_shared(&data);
+    /// assert_eq!(*view, [2, 3, 4]);
+    /// assert!(view.is_borrowed_from(&data));
+    ///
+    /// let s: Slice = "[::-2]".parse()?;
+    /// let view = s.apply_shared(&data);
+    /// assert_eq!(*view, [5, 3, 1]);
+    /// assert!(!view.is_borrowed_from(&data));
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn apply_shared<T, P>(&self, owner: &P) -> SharedSlice<P>
+    where
+        P: Deref<Target = [T]> + Clone + From<Vec<T>>,
+        T: Clone,
+    {
+        let it = self.indices(owner.len());
+        let len = it.len();
+        if len == 0 {
+            return SharedSlice {
+                owner: owner.clone(),
+                range: 0..0,
+            };
+        }
+        if len == 1 || (it.forward && it.stride == 1) {
+            return SharedSlice {
+                owner: owner.clone(),
+                range: it.next..it.next + len,
+            };
+        }
+        let copy: Vec<T> = it.map(|i| owner[i].clone()).collect();
+        SharedSlice {
+            owner: P::from(copy),
+            range: 0..len,
+        }
+    }
+}
+
+/// A contiguous range of a shared slice, which keeps the whole slice alive.
+///
+/// This `struct` is created by the `apply_shared` method on `Slice`. It dereferences to the
+/// selected elements.
+#[derive(Debug, Clone)]
+pub struct SharedSlice<P> {
+    owner: P,
+    range: Range<usize>,
+}
+
+impl<P> SharedSlice<P> {
+    /// Returns the shared slice the view points into.
+    pub fn owner(&self) -> &P {
+        &self.owner
+    }
+
+    /// Returns the range of the owner's elements the view covers.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Consumes the view, returning the shared slice it points into and the range it covers.
+    pub fn into_parts(self) -> (P, Range<usize>) {
+        (self.owner, self.range)
+    }
+}
+
+impl<T, P> SharedSlice<P>
+where
+    P: Deref<Target = [T]>,
+{
+    /// Returns true if the view points into the same allocation as `owner`, i.e. the elements
+    /// weren't copied.
+    pub fn is_borrowed_from(&self, owner: &P) -> bool {
+        core::ptr::eq(self.owner.as_ptr(), owner.as_ptr())
+    }
+}
+
+impl<T, P> Deref for SharedSlice<P>
+where
+    P: Deref<Target = [T]>,
+{
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.owner[self.range.clone()]
+    }
+}
+
+impl<T, P> AsRef<[T]> for SharedSlice<P>
+where
+    P: Deref<Target = [T]>,
+{
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Index;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[test]
+    fn apply_shared() {
+        let arc: Arc<[i32]> = Arc::from(vec![1, 2, 3, 4, 5]);
+        for &(expr, want, borrowed) in &[
+            ("[::]", &[1, 2, 3, 4, 5][..], true),
+            ("[1:-1:]", &[2, 3, 4], true),
+            ("[3:4:]", &[4], true),
+            ("[2:1:-1]", &[3], true),
+            ("[5::]", &[], true),
+            ("[::0]", &[], true),
+            ("[::2]", &[1, 3, 5], false),
+            ("[-2::-1]", &[4, 3, 2, 1], false),
+        ] {
+            let view = expr.parse::<Slice>().unwrap().apply_shared(&arc);
+            assert_eq!(*view, *want, "{}", expr);
+            assert_eq!(view.is_borrowed_from(&arc), borrowed, "{}", expr);
+        }
+
+        let rc: Rc<[i32]> = Rc::from(vec![1, 2, 3, 4, 5]);
+        let view = "[::-2]".parse::<Slice>().unwrap().apply_shared(&rc);
+        assert_eq!(*view, [5, 3, 1]);
+        assert!(!view.is_borrowed_from(&rc));
+
+        let arc: Arc<[i32]> = Arc::from(vec![1, 2, 3]);
+        let view = Slice::new(Index::Head(1), Index::Default, None).apply_shared(&arc);
+        assert_eq!(Arc::strong_count(&arc), 2);
+        assert_eq!(view.range(), 1..3);
+        drop(view);
+        assert_eq!(Arc::strong_count(&arc), 1);
+    }
+}
@@ -0,0 +1,81 @@
+//! Strided copies of numbers with SIMD gathers.
+
+use crate::Slice;
+use alloc::vec::Vec;
+use core::simd::{Simd, SimdElement};
+
+/// The number of elements gathered at once.
+const LANES: usize = 8;
+
+impl Slice {
+    /// Appends copies of the elements that match the slice expression to `dst`, like
+    /// `apply_copy_into`, gathering `LANES` elements at a time with SIMD instructions.
+    ///
+    /// This pays off for strided selections of numbers, e.g. resampling audio with a step of 2
+    /// or 4; contiguous selections are copied with `extend_from_slice` instead.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let samples: Vec<f32> = (0..64).map(|i| i as f32).collect();
+    /// let s: Slice = "[::4]".parse()?;
+    /// let mut out = Vec::new();
+    /// s.gather_into(&samples, &mut out);
+    /// assert_eq!(out.len(), 16);
+    /// assert_eq!(out[..3], [0.0, 4.0, 8.0]);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn gather_into<T>(&self, src: &[T], dst: &mut Vec<T>)
+    where
+        T: SimdElement + Default,
+    {
+        let it = self.indices(src.len());
+        let n = it.len();
+        if n > 0 && it.forward && it.stride == 1 {
+            dst.extend_from_slice(&src[it.next..it.next + n]);
+            return;
+        }
+        dst.reserve(n);
+
+        // the offsets of the lanes from the first position of a chunk, in the direction of the
+        // iteration; like `Iter::delta`, they rely on wrapping arithmetic.
+        let lane: Simd<usize, LANES> = Simd::from_array([0, 1, 2, 3, 4, 5, 6, 7]);
+        let offsets = lane * Simd::splat(it.delta);
+        let mut base = it.next;
+        for _ in 0..n / LANES {
+            let positions = Simd::splat(base) + offsets;
+            // all positions come from `indices`, so none of the lanes falls back to the default.
+            let chunk = Simd::<T, LANES>::gather_or_default(src, positions);
+            dst.extend_from_slice(chunk.as_array());
+            base = base.wrapping_add(LANES.wrapping_mul(it.delta));
+        }
+        for _ in 0..n % LANES {
+            dst.push(src[base]);
+            base = base.wrapping_add(it.delta);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gather_into() {
+        for len in [0, 1, 7, 8, 9, 31, 64, 100] {
+            let v: Vec<u32> = (0..len).collect();
+            for start in -9..9 {
+                for end in [-70, -3, 0, 5, 50, 200] {
+                    for step in -5..6 {
+                        let s = Slice::new(start.into(), end.into(), Some(step));
+                        let mut want = vec![7];
+                        s.apply_copy_into(&v, &mut want);
+                        let mut got = vec![7];
+                        s.gather_into(&v, &mut got);
+                        assert_eq!(got, want, "{} on {}", s, len);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! A trait that lets any indexable container be sliced.
+
+use crate::Slice;
+#[cfg(feature = "alloc")]
+use alloc::{collections::VecDeque, vec::Vec};
+use core::ops;
+
+/// A container with a length whose elements can be accessed by position.
+///
+/// Implementing this trait is enough for a container to be sliced with `Slice::apply_any`.
+///
+/// # Example
+/// ```
+/// use slyce::{Slice, Sliceable};
+///
+/// /// The integers from 0 to 99, squared.
+/// struct Squares([u32; 100]);
+///
+/// impl Sliceable for Squares {
+///     type Item = u32;
+///
+///     fn len(&self) -> usize {
+///         self.0.len()
+///     }
+///
+///     fn get(&self, i: usize) -> &u32 {
+///         &self.0[i]
+///     }
+/// }
+///
+/// let mut squares = Squares([0; 100]);
+/// for (i, x) in squares.0.iter_mut().enumerate() {
+///     *x = (i * i) as u32;
+/// }
+/// let s: Slice = "[-2::]".parse()?;
+/// assert_eq!(s.apply_any(&squares).collect::<Vec<_>>(), vec![&9604, &9801]);
+/// # Ok::<(), slyce::ParseError>(())
+/// ```
+pub trait Sliceable {
+    /// The type of the elements.
+    type Item: ?Sized;
+
+    /// Returns the number of elements.
+    fn len(&self) -> usize;
+
+    /// Returns true if there are no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at position `i`, which is always lower than `len()`.
+    fn get(&self, i: usize) -> &Self::Item;
+}
+
+impl<T> Sliceable for [T] {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn get(&self, i: usize) -> &T {
+        &self[i]
+    }
+}
+
+impl<T, const N: usize> Sliceable for [T; N] {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        N
+    }
+
+    fn get(&self, i: usize) -> &T {
+        &self[i]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Sliceable for Vec<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn get(&self, i: usize) -> &T {
+        &self[i]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Sliceable for VecDeque<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+
+    fn get(&self, i: usize) -> &T {
+        &self[i]
+    }
+}
+
+impl Slice {
+    /// Returns an iterator that yields the elements of any `Sliceable` container that match the
+    /// slice expression.
+    pub fn apply_any<'a, S>(&self, s: &'a S) -> impl Iterator<Item = &'a S::Item> + 'a
+    where
+        S: Sliceable + ?Sized,
+    {
+        self.indices(s.len()).map(move |i| s.get(i))
+    }
+
+    /// Returns an iterator that yields the elements of a container of length `len` that match
+    /// the slice expression, reading them with its `ops::Index<usize>` impl.
+    ///
+    /// This slices types that can be indexed but have no `len` method of their own, or don't
+    /// implement `Sliceable`, such as matrices and column stores.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `c` panics when indexed with a position lower than `len`.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// use std::ops::Index;
+    ///
+    /// /// A column of a row-major matrix with 3 columns.
+    /// struct Column<'a>(&'a [i32], usize);
+    ///
+    /// impl Index<usize> for Column<'_> {
+    ///     type Output = i32;
+    ///
+    ///     fn index(&self, row: usize) -> &i32 {
+    ///         &self.0[row * 3 + self.1]
+    ///     }
+    /// }
+    ///
+    /// let m = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+    /// let s: Slice = "[::-1]".parse()?;
+    /// assert_eq!(s.apply_indexable(&Column(&m, 1), 3).collect::<Vec<_>>(), vec![&8, &5, &2]);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn apply_indexable<'a, I>(
+        &self,
+        c: &'a I,
+        len: usize,
+    ) -> impl Iterator<Item = &'a I::Output> + 'a
+    where
+        I: ops::Index<usize> + ?Sized,
+    {
+        self.indices(len).map(move |i| &c[i])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn apply_any() {
+        let s: Slice = "[::-2]".parse().unwrap();
+        let arr = [1, 2, 3, 4, 5];
+        let want = vec![&5, &3, &1];
+        assert_eq!(s.apply_any(&arr).collect::<Vec<_>>(), want);
+        assert_eq!(s.apply_any(&arr[..]).collect::<Vec<_>>(), want);
+    }
+
+    #[test]
+    fn apply_indexable() {
+        let s: Slice = "[::-2]".parse().unwrap();
+        let arr = [1, 2, 3, 4, 5];
+        let want = vec![&5, &3, &1];
+        assert_eq!(s.apply_indexable(&arr, arr.len()).collect::<Vec<_>>(), want);
+        assert_eq!(
+            s.apply_indexable(&arr[..], 3).collect::<Vec<_>>(),
+            vec![&3, &1]
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn apply_any_collections() {
+        let s: Slice = "[::-2]".parse().unwrap();
+        let arr = [1, 2, 3, 4, 5];
+        let want = vec![&5, &3, &1];
+        assert_eq!(s.apply_any(&arr.to_vec()).collect::<Vec<_>>(), want);
+
+        let d: VecDeque<i32> = arr.iter().copied().collect();
+        assert_eq!(s.apply_any(&d).collect::<Vec<_>>(), want);
+    }
+}
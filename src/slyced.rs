@@ -0,0 +1,213 @@
+//! A `Vec` wrapper that can be indexed with slices, python style.
+
+use crate::{Elements, Iter, Slice};
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+use core::ops::{Deref, DerefMut, Index, IndexMut, Range};
+
+/// A `Vec` that can be indexed with a `Slice`, as in `&v[s]`.
+///
+/// `Index` has to return a reference to something that already exists, so indexing can only
+/// return the elements of a contiguous selection, as a sub-slice; it panics on strided or
+/// reversed selections. The `slyced` method returns a lazy view of any selection instead.
+///
+/// # Example
+/// ```
+/// use slyce::{Slice, SlyceVec};
+/// let mut v = SlyceVec::from(vec![1, 2, 3, 4, 5]);
+///
+/// let s: Slice = "[1:-1]".parse()?;
+/// assert_eq!(&v[s.clone()], &[2, 3, 4]);
+/// v[s].fill(0);
+/// assert_eq!(*v, vec![1, 0, 0, 0, 5]);
+///
+/// let s: Slice = "[::-2]".parse()?;
+/// let view = v.slyced(&s);
+/// assert_eq!(view.len(), 3);
+/// assert_eq!(view[0], 5);
+/// assert_eq!(view.iter().collect::<Vec<_>>(), vec![&5, &0, &1]);
+/// # Ok::<(), slyce::ParseError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SlyceVec<T>(pub Vec<T>);
+
+impl<T> SlyceVec<T> {
+    /// Returns the wrapped `Vec`.
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+
+    /// Returns a lazy view of the elements selected by `slice`, in selection order.
+    pub fn slyced(&self, slice: &Slice) -> Slyced<'_, T> {
+        Slyced {
+            arr: &self.0,
+            indices: slice.indices(self.0.len()),
+        }
+    }
+
+    /// Returns the positions of the elements selected by `slice`, which must be contiguous and
+    /// in order.
+    fn contiguous(&self, slice: &Slice) -> Range<usize> {
+        let it = slice.indices(self.0.len());
+        let len = it.len();
+        assert!(
+            len <= 1 || (it.forward && it.stride == 1),
+            "cannot index with {}, which selects non-contiguous elements; use `slyced`",
+            slice
+        );
+        match len {
+            0 => 0..0,
+            _ => it.next..it.next + len,
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for SlyceVec<T> {
+    fn from(v: Vec<T>) -> Self {
+        SlyceVec(v)
+    }
+}
+
+impl<T> FromIterator<T> for SlyceVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        SlyceVec(iter.into_iter().collect())
+    }
+}
+
+impl<T> Deref for SlyceVec<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for SlyceVec<T> {
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        &mut self.0
+    }
+}
+
+impl<T> AsRef<[T]> for SlyceVec<T> {
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> AsMut<[T]> for SlyceVec<T> {
+    fn as_mut(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+}
+
+impl<T> Index<Slice> for SlyceVec<T> {
+    type Output = [T];
+
+    /// Returns the elements selected by `slice` as a sub-slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slice` selects more than one element with a step other than 1.
+    fn index(&self, slice: Slice) -> &[T] {
+        &self.0[self.contiguous(&slice)]
+    }
+}
+
+impl<T> IndexMut<Slice> for SlyceVec<T> {
+    /// Returns the elements selected by `slice` as a mutable sub-slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slice` selects more than one element with a step other than 1.
+    fn index_mut(&mut self, slice: Slice) -> &mut [T] {
+        let range = self.contiguous(&slice);
+        &mut self.0[range]
+    }
+}
+
+/// A lazy view of the elements of a `SlyceVec` selected by a slice.
+///
+/// This `struct` is created by the `slyced` method on `SlyceVec`. Indexing it with `n` returns
+/// the `n`-th selected element, in constant time.
+#[derive(Debug, Clone)]
+pub struct Slyced<'a, T> {
+    arr: &'a [T],
+    indices: Iter,
+}
+
+impl<'a, T> Slyced<'a, T> {
+    /// Returns the number of selected elements.
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns true if no element is selected.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the `n`-th selected element, or `None` if fewer are selected.
+    pub fn get(&self, n: usize) -> Option<&'a T> {
+        self.indices.clone().nth(n).map(|i| &self.arr[i])
+    }
+
+    /// Returns an iterator that yields the selected elements.
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a {
+        Elements::new(self.arr, self.indices.clone())
+    }
+
+    /// Returns a vector with clones of the selected elements.
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<'a, T> Index<usize> for Slyced<'a, T> {
+    type Output = T;
+
+    fn index(&self, n: usize) -> &T {
+        match self.get(n) {
+            Some(x) => x,
+            None => panic!(
+                "index out of bounds: the view has {} elements but the index is {}",
+                self.len(),
+                n
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn index() {
+        let v: SlyceVec<i32> = (0..5).collect();
+        let parse = |expr: &str| expr.parse::<Slice>().unwrap();
+        assert_eq!(v[parse("[1:-1:]")], [1, 2, 3]);
+        assert_eq!(v[parse("[-2:]")], [3, 4]);
+        // a single element is contiguous whatever the step.
+        assert_eq!(v[parse("[3:2:-1]")], [3]);
+        assert_eq!(v[parse("[2::9]")], [2]);
+        assert!(v[parse("[4:1:]")].is_empty());
+
+        let view = v.slyced(&parse("[::-2]"));
+        assert_eq!((view.len(), view[0], view[2]), (3, 4, 0));
+        assert_eq!((view.get(1), view.get(3)), (Some(&2), None));
+        assert_eq!(view.to_vec(), [4, 2, 0]);
+        let view = v.slyced(&parse("[::0]"));
+        assert!(view.is_empty());
+        assert_eq!(view.get(0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot index with [::2]")]
+    fn index_strided() {
+        let v = SlyceVec::from(vec![1, 2, 3]);
+        let _ = &v["[::2]".parse::<Slice>().unwrap()];
+    }
+}
@@ -0,0 +1,116 @@
+//! Conversion of slices into SQL array subscripts.
+
+use crate::{Error, Index, Slice};
+use alloc::format;
+use alloc::string::String;
+
+/// The SQL engines whose array slice syntax `Slice::to_sql` can produce.
+///
+/// Both use 1-based positions and inclusive upper bounds, and clip slices that fall partially
+/// outside of the array; they differ in how positions relative to the end are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    /// PostgreSQL 9.6 or later. Positions relative to the end are computed with
+    /// `array_length`, which is NULL for empty arrays, so such slices of an empty array are
+    /// NULL rather than empty.
+    Postgres,
+    /// DuckDB, which supports negative positions.
+    DuckDb,
+}
+
+impl Slice {
+    /// Renders the slice as an array subscript applied to the SQL expression `array`, e.g.
+    /// `[1:-1]` as `arr[2:array_length(arr, 1) - 1]` for PostgreSQL.
+    ///
+    /// Neither engine supports steps, so `Error::UnsupportedStep` is returned unless the step
+    /// is omitted or 1.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::{Slice, SqlDialect};
+    /// let s: Slice = "[1:5]".parse()?;
+    /// assert_eq!(s.to_sql("arr", SqlDialect::Postgres)?, "arr[2:5]");
+    /// let s: Slice = "[-3:]".parse()?;
+    /// assert_eq!(s.to_sql("arr", SqlDialect::DuckDb)?, "arr[-3:]");
+    /// assert!("[::2]".parse::<Slice>()?.to_sql("arr", SqlDialect::DuckDb).is_err());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_sql(&self, array: &str, dialect: SqlDialect) -> Result<String, Error> {
+        match self.step {
+            None | Some(1) => {}
+            Some(step) => return Err(Error::UnsupportedStep { step }),
+        }
+        let len = || match dialect {
+            SqlDialect::Postgres => format!("array_length({}, 1)", array),
+            SqlDialect::DuckDb => format!("len({})", array),
+        };
+        // the 1-based position `k` places before the last element. Positions are widened so
+        // that converting them to 1-based can't overflow.
+        let from_end = |k: u128| match (dialect, k) {
+            (SqlDialect::DuckDb, k) => format!("-{}", k + 1),
+            (SqlDialect::Postgres, 0) => len(),
+            (SqlDialect::Postgres, k) => format!("{} - {}", len(), k),
+        };
+        let start = match self.start {
+            Index::Head(n) => format!("{}", n as u128 + 1),
+            // the first element past the end.
+            Index::Tail(0) => format!("{} + 1", len()),
+            Index::Tail(n) => from_end(n as u128 - 1),
+            Index::Default => String::new(),
+        };
+        let end = match self.end {
+            Index::Head(n) => format!("{}", n),
+            Index::Tail(0) | Index::Default => String::new(),
+            Index::Tail(n) => from_end(n as u128),
+        };
+        Ok(format!("{}[{}:{}]", array, start, end))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sql(expr: &str, dialect: SqlDialect) -> String {
+        expr.parse::<Slice>().unwrap().to_sql("a", dialect).unwrap()
+    }
+
+    #[test]
+    fn postgres() {
+        let pg = |expr| sql(expr, SqlDialect::Postgres);
+        assert_eq!(pg("[::]"), "a[:]");
+        assert_eq!(pg("[0:3]"), "a[1:3]");
+        assert_eq!(pg("[2:]"), "a[3:]");
+        assert_eq!(pg("[:0]"), "a[:0]");
+        assert_eq!(pg("[-1:]"), "a[array_length(a, 1):]");
+        assert_eq!(
+            pg("[-3:-1]"),
+            "a[array_length(a, 1) - 2:array_length(a, 1) - 1]"
+        );
+        assert_eq!(pg("[18446744073709551615:]"), "a[18446744073709551616:]");
+    }
+
+    #[test]
+    fn duckdb() {
+        let duck = |expr| sql(expr, SqlDialect::DuckDb);
+        assert_eq!(duck("[1:-1]"), "a[2:-2]");
+        assert_eq!(duck("[-2:]"), "a[-2:]");
+        assert_eq!(duck("[:-3:1]"), "a[:-4]");
+        let past_end = Slice::new(Index::Tail(0), Index::Default, None);
+        assert_eq!(
+            past_end.to_sql("a", SqlDialect::DuckDb).unwrap(),
+            "a[len(a) + 1:]"
+        );
+    }
+
+    #[test]
+    fn unsupported_step() {
+        for expr in &["[::-1]", "[::2]", "[::0]"] {
+            let s: Slice = expr.parse().unwrap();
+            assert!(matches!(
+                s.to_sql("a", SqlDialect::Postgres),
+                Err(Error::UnsupportedStep { .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! `proptest` strategies for indices and slices.
+
+use crate::{Index, Slice};
+use proptest::prelude::*;
+
+impl Arbitrary for Index {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Generates indices that are mostly small, so that they're often within the length of the
+    /// sliced sequence, but occasionally of any magnitude. `Tail(0)` is never generated.
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![
+            1 => Just(Index::Default),
+            4 => (0..16usize).prop_map(Index::Head),
+            4 => (1..=16usize).prop_map(Index::Tail),
+            1 => any::<usize>().prop_map(Index::Head),
+            1 => (1..=usize::MAX).prop_map(Index::Tail),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for Slice {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Generates slices made of arbitrary indices and a step that's mostly small.
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let step = prop_oneof![
+            1 => Just(None),
+            4 => (-4..=4isize).prop_map(Some),
+            1 => any::<isize>().prop_map(Some),
+        ];
+        (any::<Index>(), any::<Index>(), step)
+            .prop_map(|(start, end, step)| Slice { start, end, step })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::oracle;
+
+    proptest! {
+        #[test]
+        fn no_tail_zero(i in any::<Index>()) {
+            prop_assert_ne!(i, Index::Tail(0));
+        }
+
+        #[test]
+        fn apply_matches_oracle(s in any::<Slice>(), len in 0..32usize) {
+            let v: Vec<usize> = (0..len).collect();
+            let got: Vec<&usize> = s.apply(&v).collect();
+            prop_assert_eq!(got, oracle::apply(&s, &v));
+        }
+    }
+}
@@ -0,0 +1,574 @@
+//! Slicing of iterators whose length is not known in advance.
+
+use crate::{Index, Slice};
+use alloc::collections::VecDeque;
+use alloc::vec::{self, Vec};
+
+impl Slice {
+    /// Returns an iterator that yields the items of `iter` selected by the slice, without
+    /// knowing the length of `iter` in advance.
+    ///
+    /// With a positive step and a start relative to the head, items are yielded as soon as they
+    /// are known to be selected: an end `Tail(m)` delays them by at most `m` items, and an end
+    /// `Head(n)` stops reading from `iter` once it's reached.
+    ///
+    /// Otherwise the selection can only be resolved once `iter` is exhausted, so the candidate
+    /// items are buffered until then. The buffer holds at most `n` items when the lower bound of
+    /// the selection is `Tail(n)`; when the lower bound is relative to the head (e.g. a negative
+    /// step with a default end) all items after it are buffered.
+    pub fn apply_iter<I>(&self, iter: I) -> StreamIter<I::IntoIter>
+    where
+        I: IntoIterator,
+    {
+        StreamIter {
+            inner: iter.into_iter(),
+            selection: Selection::new(self),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Slice {
+    /// Returns an iterator that yields the lines of `reader` selected by the slice, without
+    /// their line terminators.
+    ///
+    /// The same buffering rules as `apply_iter` apply: a forward selection stops reading as soon
+    /// as it's complete, while a selection relative to the end of the input only buffers as many
+    /// lines as its tail bound requires (e.g. 10 for `[-10::]`).
+    ///
+    /// An I/O error is yielded as soon as it occurs, after which the iteration ends.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let s: Slice = "[-2::]".parse()?;
+    /// let text = "one\ntwo\nthree\n";
+    /// let lines = s.apply_lines(text.as_bytes()).collect::<std::io::Result<Vec<_>>>()?;
+    /// assert_eq!(lines, vec!["two", "three"]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn apply_lines<R>(&self, reader: R) -> SlicedLines<R>
+    where
+        R: std::io::BufRead,
+    {
+        SlicedLines {
+            lines: reader.lines(),
+            selection: Selection::new(self),
+        }
+    }
+}
+
+#[cfg(all(feature = "csv", feature = "std"))]
+impl Slice {
+    /// Returns an iterator that yields the records of `reader` selected by the slice, i.e. the
+    /// selected rows. The header row, if the reader has one, is not part of the selection.
+    ///
+    /// The same buffering rules as `apply_iter` apply: a forward selection stops reading as soon
+    /// as it's complete, while a selection relative to the end of the input only buffers as many
+    /// records as its tail bound requires.
+    ///
+    /// A malformed record is yielded as an error as soon as it's read, even if it's not
+    /// selected, after which the iteration ends.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let data = "id,name\n1,a\n2,b\n3,c\n";
+    /// let mut reader = csv::Reader::from_reader(data.as_bytes());
+    /// let s: Slice = "[-2:]".parse()?;
+    /// let rows = s.apply_csv(&mut reader).collect::<csv::Result<Vec<_>>>()?;
+    /// assert_eq!(rows, vec![vec!["2", "b"], vec!["3", "c"]]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn apply_csv<'r, R>(&self, reader: &'r mut csv::Reader<R>) -> SlicedRecords<'r, R>
+    where
+        R: std::io::Read,
+    {
+        SlicedRecords {
+            records: reader.records(),
+            selection: Selection::new(self),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Slice {
+    /// Returns a stream that yields the lines of `reader` selected by the slice, without their
+    /// line terminators.
+    ///
+    /// This is the asynchronous equivalent of `apply_lines`: the same buffering rules apply,
+    /// `reader` is no longer read as soon as the selection is complete, and an I/O error is
+    /// yielded as soon as it occurs, after which the stream ends.
+    pub fn apply_lines_async<R>(
+        &self,
+        reader: R,
+    ) -> impl futures::Stream<Item = std::io::Result<String>>
+    where
+        R: tokio::io::AsyncBufRead + Unpin,
+    {
+        use tokio::io::AsyncBufReadExt;
+
+        let state = (reader.lines(), Selection::new(self));
+        futures::stream::unfold(state, |(mut lines, mut selection)| async move {
+            loop {
+                if let Some(line) = selection.pop() {
+                    return Some((Ok(line), (lines, selection)));
+                }
+                if !selection.wants_input() {
+                    selection.finish();
+                    return selection.pop().map(|line| (Ok(line), (lines, selection)));
+                }
+                match lines.next_line().await {
+                    Ok(Some(line)) => selection.push(line),
+                    Ok(None) => selection.finish(),
+                    Err(e) => {
+                        selection.mode = Mode::Done;
+                        return Some((Err(e), (lines, selection)));
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(feature = "futures")]
+impl Slice {
+    /// Returns a stream that yields the items of `stream` selected by the slice.
+    ///
+    /// This is the asynchronous equivalent of `apply_iter`: the same buffering rules apply, and
+    /// `stream` is no longer polled as soon as the selection is complete.
+    pub fn apply_stream<S>(&self, stream: S) -> impl futures::Stream<Item = S::Item>
+    where
+        S: futures::Stream,
+    {
+        use futures::StreamExt;
+
+        let state = (alloc::boxed::Box::pin(stream), Selection::new(self));
+        futures::stream::unfold(state, |(mut stream, mut selection)| async move {
+            loop {
+                if let Some(x) = selection.pop() {
+                    return Some((x, (stream, selection)));
+                }
+                if !selection.wants_input() {
+                    selection.finish();
+                    return selection.pop().map(|x| (x, (stream, selection)));
+                }
+                match stream.next().await {
+                    Some(x) => selection.push(x),
+                    None => selection.finish(),
+                }
+            }
+        })
+    }
+}
+
+fn head(i: &Index) -> Option<usize> {
+    match *i {
+        Index::Head(n) => Some(n),
+        _ => None,
+    }
+}
+
+fn tail(i: &Index) -> Option<usize> {
+    match *i {
+        Index::Tail(n) => Some(n),
+        _ => None,
+    }
+}
+
+/// An iterator that yields the items of an iterator of unknown length selected by a slice.
+///
+/// This `struct` is created by the `apply_iter` method on `Slice`.
+pub struct StreamIter<I>
+where
+    I: Iterator,
+{
+    inner: I,
+    selection: Selection<I::Item>,
+}
+
+impl<I> Iterator for StreamIter<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        loop {
+            if let Some(x) = self.selection.pop() {
+                return Some(x);
+            }
+            if !self.selection.wants_input() {
+                self.selection.finish();
+                return self.selection.pop();
+            }
+            match self.inner.next() {
+                Some(x) => self.selection.push(x),
+                None => self.selection.finish(),
+            }
+        }
+    }
+}
+
+/// An iterator over the lines of a reader selected by a slice.
+///
+/// This `struct` is created by the `apply_lines` method on `Slice`.
+#[cfg(feature = "std")]
+pub struct SlicedLines<R> {
+    lines: std::io::Lines<R>,
+    selection: Selection<String>,
+}
+
+#[cfg(feature = "std")]
+impl<R> Iterator for SlicedLines<R>
+where
+    R: std::io::BufRead,
+{
+    type Item = std::io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(line) = self.selection.pop() {
+                return Some(Ok(line));
+            }
+            if !self.selection.wants_input() {
+                self.selection.finish();
+                return self.selection.pop().map(Ok);
+            }
+            match self.lines.next() {
+                Some(Ok(line)) => self.selection.push(line),
+                Some(Err(e)) => {
+                    self.selection.mode = Mode::Done;
+                    return Some(Err(e));
+                }
+                None => self.selection.finish(),
+            }
+        }
+    }
+}
+
+/// An iterator over the records of a CSV reader selected by a slice.
+///
+/// This `struct` is created by the `apply_csv` method on `Slice`.
+#[cfg(all(feature = "csv", feature = "std"))]
+pub struct SlicedRecords<'r, R> {
+    records: csv::StringRecordsIter<'r, R>,
+    selection: Selection<csv::StringRecord>,
+}
+
+#[cfg(all(feature = "csv", feature = "std"))]
+impl<R> Iterator for SlicedRecords<'_, R>
+where
+    R: std::io::Read,
+{
+    type Item = csv::Result<csv::StringRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(record) = self.selection.pop() {
+                return Some(Ok(record));
+            }
+            if !self.selection.wants_input() {
+                self.selection.finish();
+                return self.selection.pop().map(Ok);
+            }
+            match self.records.next() {
+                Some(Ok(record)) => self.selection.push(record),
+                Some(Err(e)) => {
+                    self.selection.mode = Mode::Done;
+                    return Some(Err(e));
+                }
+                None => self.selection.finish(),
+            }
+        }
+    }
+}
+
+/// The state of a slice being applied to a sequence of unknown length, which is fed one item at
+/// a time.
+struct Selection<T> {
+    slice: Slice,
+    pos: usize,
+    mode: Mode<T>,
+}
+
+enum Mode<T> {
+    /// Items are yielded while reading; `pending` holds selected items that might still turn out
+    /// to be within the last `lag` items.
+    Streaming {
+        start: usize,
+        stop: Option<usize>,
+        lag: usize,
+        step: usize,
+        pending: VecDeque<(usize, T)>,
+    },
+    /// Items in positions `from..to` are buffered until the length is known, keeping only the
+    /// last `window` ones if set.
+    Buffering {
+        from: usize,
+        to: Option<usize>,
+        window: Option<usize>,
+        exhaust: bool,
+        buffer: VecDeque<(usize, T)>,
+    },
+    Draining(vec::IntoIter<T>),
+    Done,
+}
+
+impl<T> Selection<T> {
+    fn new(slice: &Slice) -> Self {
+        let step = slice.step.unwrap_or(1);
+        let (lower, upper) = if step >= 0 {
+            (&slice.start, &slice.end)
+        } else {
+            (&slice.end, &slice.start)
+        };
+        let mode = match (step, lower, upper) {
+            (0, _, _) => Mode::Done,
+            (s, &Index::Head(_), _) | (s, &Index::Default, _) if s > 0 => Mode::Streaming {
+                start: head(&slice.start).unwrap_or(0),
+                stop: head(&slice.end),
+                lag: tail(&slice.end).unwrap_or(0),
+                step: s as usize,
+                pending: VecDeque::new(),
+            },
+            _ => Mode::Buffering {
+                // a backward lower bound is exclusive.
+                from: head(lower).map_or(0, |n| if step > 0 { n } else { n.saturating_add(1) }),
+                to: head(upper).map(|n| if step > 0 { n } else { n.saturating_add(1) }),
+                window: tail(lower).map(|n| if step > 0 { n } else { n.saturating_sub(1) }),
+                exhaust: tail(&slice.start).is_some() || tail(&slice.end).is_some(),
+                buffer: VecDeque::new(),
+            },
+        };
+        Selection {
+            slice: slice.clone(),
+            pos: 0,
+            mode,
+        }
+    }
+
+    /// Returns the next selected item, if it's already known.
+    fn pop(&mut self) -> Option<T> {
+        match self.mode {
+            Mode::Streaming {
+                lag,
+                ref mut pending,
+                ..
+            } => match pending.front() {
+                Some(&(i, _)) if self.pos - i > lag => pending.pop_front().map(|(_, x)| x),
+                _ => None,
+            },
+            Mode::Draining(ref mut it) => it.next(),
+            _ => None,
+        }
+    }
+
+    /// Returns false when no further item can affect the selection.
+    fn wants_input(&self) -> bool {
+        match self.mode {
+            Mode::Streaming {
+                stop, ref pending, ..
+            } => !pending.is_empty() || stop.map_or(true, |stop| self.pos < stop),
+            Mode::Buffering { to, exhaust, .. } => exhaust || to.map_or(true, |to| self.pos < to),
+            _ => false,
+        }
+    }
+
+    /// Feeds the next item of the input.
+    fn push(&mut self, x: T) {
+        let i = self.pos;
+        self.pos += 1;
+        match self.mode {
+            Mode::Streaming {
+                start,
+                stop,
+                step,
+                ref mut pending,
+                ..
+            } if i >= start && stop.map_or(true, |stop| i < stop) && (i - start) % step == 0 => {
+                pending.push_back((i, x));
+            }
+            Mode::Buffering {
+                from,
+                to,
+                window,
+                ref mut buffer,
+                ..
+            } => {
+                if i >= from && to.map_or(true, |to| i < to) {
+                    buffer.push_back((i, x));
+                }
+                if let Some(window) = window {
+                    while buffer.front().map_or(false, |&(j, _)| i - j >= window) {
+                        buffer.pop_front();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Signals that no more items will be fed, resolving the buffered selection.
+    fn finish(&mut self) {
+        match self.mode {
+            Mode::Streaming { .. } => self.mode = Mode::Done,
+            Mode::Buffering { ref mut buffer, .. } => {
+                let base = buffer.front().map_or(0, |&(i, _)| i);
+                let mut items: Vec<_> = buffer.drain(..).map(|(_, x)| Some(x)).collect();
+                let selected: Vec<_> = self
+                    .slice
+                    .indices(self.pos)
+                    .filter_map(|i| items.get_mut(i.checked_sub(base)?)?.take())
+                    .collect();
+                self.mode = Mode::Draining(selected.into_iter());
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn apply_iter() {
+        for len in 0..8 {
+            let v: Vec<usize> = (0..len).collect();
+            for start in -9..9 {
+                for end in -9..9 {
+                    for step in -4..4 {
+                        let (start, end) = (Some(start).into(), Some(end).into());
+                        let s = Slice {
+                            start,
+                            end,
+                            step: Some(step),
+                        };
+                        let want: Vec<usize> = s.apply(&v).copied().collect();
+                        let got: Vec<usize> = s.apply_iter(v.iter().copied()).collect();
+                        assert_eq!(got, want, "{} on {:?}", s, v);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn apply_iter_is_lazy() {
+        let s: Slice = "[2:5:]".parse().unwrap();
+        assert_eq!(s.apply_iter(0..).collect::<Vec<_>>(), vec![2, 3, 4]);
+
+        let s: Slice = "[4:1:-1]".parse().unwrap();
+        assert_eq!(s.apply_iter(0..).collect::<Vec<_>>(), vec![4, 3, 2]);
+
+        let s: Slice = "[1::3]".parse().unwrap();
+        assert_eq!(s.apply_iter(0..).take(3).collect::<Vec<_>>(), vec![1, 4, 7]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn apply_lines() {
+        use std::io::{self, BufRead, BufReader, Read};
+
+        let text: String = (0..100).map(|i| format!("line {}\n", i)).collect();
+        let lines = |expr: &str| {
+            let s: Slice = expr.parse().unwrap();
+            s.apply_lines(text.as_bytes())
+                .collect::<io::Result<Vec<_>>>()
+                .unwrap()
+        };
+        assert_eq!(lines("[-2::]"), ["line 98", "line 99"]);
+        assert_eq!(lines("[10:40:10]"), ["line 10", "line 20", "line 30"]);
+        assert_eq!(lines("[3:0:-1]"), ["line 3", "line 2", "line 1"]);
+
+        // reading stops once a forward selection is complete.
+        let mut reader = BufReader::with_capacity(8, text.as_bytes());
+        let s: Slice = "[:2:]".parse().unwrap();
+        assert_eq!(s.apply_lines(&mut reader).count(), 2);
+        assert_eq!(reader.lines().next().unwrap().unwrap(), "line 2");
+
+        // errors are yielded and end the iteration.
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::Other, "boom"))
+            }
+        }
+        let s: Slice = "[::]".parse().unwrap();
+        let mut it = s.apply_lines(BufReader::new(Failing));
+        assert!(it.next().unwrap().is_err());
+        assert!(it.next().is_none());
+    }
+
+    #[cfg(all(feature = "csv", feature = "std"))]
+    #[test]
+    fn apply_csv() {
+        let data: String = (0..100).map(|i| format!("{},{}\n", i, i * i)).collect();
+        let rows = |expr: &str| {
+            let s: Slice = expr.parse().unwrap();
+            let mut reader = csv::ReaderBuilder::new()
+                .has_headers(false)
+                .from_reader(data.as_bytes());
+            s.apply_csv(&mut reader)
+                .map(|r| r.unwrap()[1].to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(rows("[-2::]"), ["9604", "9801"]);
+        assert_eq!(rows("[2:5:2]"), ["4", "16"]);
+        assert_eq!(rows("[3:0:-1]"), ["9", "4", "1"]);
+
+        // malformed records are errors even when they're not selected.
+        let mut reader = csv::Reader::from_reader("a,b\n1,2\n3\n4,5\n".as_bytes());
+        let s: Slice = "[-1::]".parse().unwrap();
+        let mut it = s.apply_csv(&mut reader);
+        assert!(it.next().unwrap().is_err());
+        assert!(it.next().is_none());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn apply_lines_async() {
+        use futures::{executor::block_on, StreamExt};
+
+        let text: String = (0..100).map(|i| format!("line {}\n", i)).collect();
+        let lines = |expr: &str| {
+            let s: Slice = expr.parse().unwrap();
+            let lines: Vec<_> = block_on(s.apply_lines_async(text.as_bytes()).collect());
+            lines
+                .into_iter()
+                .collect::<std::io::Result<Vec<_>>>()
+                .unwrap()
+        };
+        assert_eq!(lines("[-2::]"), ["line 98", "line 99"]);
+        assert_eq!(lines("[10:40:10]"), ["line 10", "line 20", "line 30"]);
+        assert_eq!(lines("[3:0:-1]"), ["line 3", "line 2", "line 1"]);
+        assert_eq!(lines("[200::]"), Vec::<String>::new());
+
+        // lines are yielded before the input ends.
+        let s: Slice = "[1:3]".parse().unwrap();
+        let input = tokio::io::BufReader::new(tokio::io::repeat(b'\n'));
+        let first: Vec<_> = block_on(s.apply_lines_async(input).take(5).collect());
+        assert_eq!(first.len(), 2);
+
+        // invalid UTF-8 is an error, after which the stream ends.
+        let s: Slice = "[-1::]".parse().unwrap();
+        let r: Vec<_> = block_on(s.apply_lines_async(&b"a\n\xff\nb\n"[..]).collect());
+        assert_eq!(r.len(), 1);
+        assert!(r[0].is_err());
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn apply_stream() {
+        use futures::{executor::block_on, stream, StreamExt};
+
+        let s: Slice = "[-4::2]".parse().unwrap();
+        let r: Vec<_> = block_on(s.apply_stream(stream::iter(0..10)).collect());
+        assert_eq!(r, vec![6, 8]);
+
+        let s: Slice = "[1:4:]".parse().unwrap();
+        let r: Vec<_> = block_on(s.apply_stream(stream::iter(0..)).collect());
+        assert_eq!(r, vec![1, 2, 3]);
+    }
+}
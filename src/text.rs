@@ -0,0 +1,303 @@
+//! Slicing of strings by character or, with the `unicode-segmentation` feature, by grapheme
+//! cluster.
+
+use crate::{Iter, Slice};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::str::Chars;
+#[cfg(feature = "unicode-segmentation")]
+use unicode_segmentation::{Graphemes, UnicodeSegmentation};
+
+impl Slice {
+    /// Returns an iterator over the characters of `s` that match the slice expression, where
+    /// positions count `char`s rather than bytes, so that multi-byte characters are never split.
+    ///
+    /// The length of `s` in characters is computed upfront; the characters themselves are then
+    /// decoded lazily, from the end of the string if the step is negative.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let s: Slice = "[::-1]".parse()?;
+    /// assert_eq!(s.apply_str("añb").collect::<String>(), "bña");
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn apply_str<'a>(&self, s: &'a str) -> SlicedChars<'a> {
+        SlicedChars(Pick::new(self, s.chars()))
+    }
+
+    /// Returns a string with the characters of `s` that match the slice expression.
+    #[cfg(feature = "alloc")]
+    pub fn apply_to_string(&self, s: &str) -> String {
+        self.apply_str(s).collect()
+    }
+
+    /// Returns an iterator over the extended grapheme clusters of `s` that match the slice
+    /// expression, i.e. over what a reader perceives as characters, such as flags or letters
+    /// with combining accents, which span several `char`s.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let s: Slice = "[-2::]".parse()?;
+    /// assert_eq!(s.apply_graphemes("a🇺🇸👍🏽").collect::<String>(), "🇺🇸👍🏽");
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    #[cfg(feature = "unicode-segmentation")]
+    pub fn apply_graphemes<'a>(&self, s: &'a str) -> SlicedGraphemes<'a> {
+        SlicedGraphemes(Pick::new(self, s.graphemes(true)))
+    }
+}
+
+/// Yields the items of a double-ended iterator at the positions selected by a slice, walking
+/// it from the back if the step is negative.
+#[derive(Clone)]
+struct Pick<I> {
+    inner: I,
+    indices: Iter,
+    /// The position of the next item of `inner` when walking forward, or one past the
+    /// position of the last one when walking backward.
+    pos: usize,
+}
+
+impl<I> Pick<I>
+where
+    I: DoubleEndedIterator + Clone,
+{
+    fn new(slice: &Slice, inner: I) -> Self {
+        let len = inner.clone().count();
+        Pick {
+            inner,
+            indices: slice.indices(len),
+            pos: if slice.step_or_default() > 0 { 0 } else { len },
+        }
+    }
+}
+
+impl<I> Iterator for Pick<I>
+where
+    I: DoubleEndedIterator,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let i = self.indices.next()?;
+        if self.indices.forward {
+            let x = self.inner.nth(i - self.pos);
+            self.pos = i + 1;
+            x
+        } else {
+            let x = self.inner.nth_back(self.pos - 1 - i);
+            self.pos = i;
+            x
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+/// An iterator over the characters of a string selected by a slice.
+///
+/// This `struct` is created by the `apply_str` method on `Slice`.
+#[derive(Clone)]
+pub struct SlicedChars<'a>(Pick<Chars<'a>>);
+
+impl<'a> Iterator for SlicedChars<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for SlicedChars<'a> {}
+
+/// An iterator over the grapheme clusters of a string selected by a slice.
+///
+/// This `struct` is created by the `apply_graphemes` method on `Slice`.
+#[cfg(feature = "unicode-segmentation")]
+#[derive(Clone)]
+pub struct SlicedGraphemes<'a>(Pick<Graphemes<'a>>);
+
+#[cfg(feature = "unicode-segmentation")]
+impl<'a> Iterator for SlicedGraphemes<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+#[cfg(feature = "unicode-segmentation")]
+impl<'a> ExactSizeIterator for SlicedGraphemes<'a> {}
+
+/// A string together with the byte offsets of its characters or grapheme clusters, so that it
+/// can be sliced repeatedly without scanning it each time.
+///
+/// Building the index takes time proportional to the length of the string; afterwards, each
+/// application of a slice takes time proportional to the number of selected elements.
+///
+/// # Example
+/// ```
+/// use slyce::{Slice, StrIndex};
+/// let index = StrIndex::chars("añ€😀b");
+/// let s: Slice = "[1::2]".parse()?;
+/// assert_eq!(index.apply_to_string(&s), "ñ😀");
+/// # Ok::<(), slyce::ParseError>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct StrIndex<'a> {
+    text: &'a str,
+    /// The byte offset of the start of each element, followed by the length of `text`.
+    offsets: Vec<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> StrIndex<'a> {
+    /// Indexes the characters of `text`.
+    pub fn chars(text: &'a str) -> Self {
+        Self::from_offsets(text, text.char_indices().map(|(i, _)| i))
+    }
+
+    /// Indexes the extended grapheme clusters of `text`.
+    #[cfg(feature = "unicode-segmentation")]
+    pub fn graphemes(text: &'a str) -> Self {
+        Self::from_offsets(text, text.grapheme_indices(true).map(|(i, _)| i))
+    }
+
+    fn from_offsets<I: Iterator<Item = usize>>(text: &'a str, offsets: I) -> Self {
+        let mut offsets: Vec<usize> = offsets.collect();
+        offsets.push(text.len());
+        StrIndex { text, offsets }
+    }
+
+    /// Returns the number of indexed elements.
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns true if the indexed string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at position `i`, if any.
+    pub fn get(&self, i: usize) -> Option<&'a str> {
+        let (start, end) = (*self.offsets.get(i)?, *self.offsets.get(i + 1)?);
+        Some(&self.text[start..end])
+    }
+
+    /// Returns an iterator over the elements that match the slice expression.
+    pub fn apply<'s>(&'s self, slice: &Slice) -> impl Iterator<Item = &'a str> + 's {
+        let text = self.text;
+        let offsets = &self.offsets;
+        slice
+            .indices(self.len())
+            .map(move |i| &text[offsets[i]..offsets[i + 1]])
+    }
+
+    /// Returns a string with the elements that match the slice expression.
+    pub fn apply_to_string(&self, slice: &Slice) -> String {
+        self.apply(slice).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn apply_str() {
+        let text = "añ€😀b";
+        let sel = |expr: &str| {
+            expr.parse::<Slice>()
+                .unwrap()
+                .apply_str(text)
+                .collect::<String>()
+        };
+        assert_eq!(sel("[::]"), text);
+        assert_eq!(sel("[1:-1:]"), "ñ€😀");
+        assert_eq!(sel("[::2]"), "a€b");
+        assert_eq!(sel("[::-1]"), "b😀€ña");
+        assert_eq!(sel("[-2::-2]"), "😀ñ");
+        assert_eq!(sel("[9::]"), "");
+        assert_eq!(sel("[::0]"), "");
+    }
+
+    #[test]
+    fn apply_str_matches_apply() {
+        let text = "aé€😀bç";
+        let chars: Vec<char> = text.chars().collect();
+        for start in -8..8 {
+            for end in -8..8 {
+                for step in -3..3 {
+                    let s = Slice::new(start.into(), end.into(), Some(step));
+                    let want: Vec<char> = s.apply(&chars).copied().collect();
+                    let got = s.apply_str(text);
+                    assert_eq!(got.len(), want.len());
+                    assert_eq!(got.collect::<Vec<_>>(), want, "{}", s);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn apply_graphemes() {
+        // a flag, a thumb with a skin tone modifier and an e with a combining acute accent.
+        let text = "x🇺🇸👍🏽e\u{301}";
+        let sel = |expr: &str| {
+            expr.parse::<Slice>()
+                .unwrap()
+                .apply_graphemes(text)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sel("[-3::]"), ["🇺🇸", "👍🏽", "e\u{301}"]);
+        assert_eq!(sel("[::-2]"), ["e\u{301}", "🇺🇸"]);
+        assert_eq!(sel("[1:2:]"), ["🇺🇸"]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn str_index() {
+        let text = "aé€😀bç";
+        let index = StrIndex::chars(text);
+        assert_eq!(index.len(), 6);
+        assert_eq!(index.get(3), Some("😀"));
+        assert_eq!(index.get(6), None);
+        for start in -8..8 {
+            for end in -8..8 {
+                for step in -3..3 {
+                    let s = Slice::new(start.into(), end.into(), Some(step));
+                    let want: String = s.apply_str(text).collect();
+                    assert_eq!(index.apply_to_string(&s), want, "{}", s);
+                }
+            }
+        }
+        assert!(StrIndex::chars("").is_empty());
+    }
+
+    #[cfg(all(feature = "alloc", feature = "unicode-segmentation"))]
+    #[test]
+    fn str_index_graphemes() {
+        let text = "x🇺🇸👍🏽e\u{301}";
+        let index = StrIndex::graphemes(text);
+        assert_eq!(index.len(), 4);
+        let s: Slice = "[::-1]".parse().unwrap();
+        assert_eq!(
+            index.apply(&s).collect::<Vec<_>>(),
+            s.apply_graphemes(text).collect::<Vec<_>>()
+        );
+    }
+}
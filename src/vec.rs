@@ -0,0 +1,467 @@
+//! Operations that produce or modify a `Vec` through a slice.
+
+use crate::{Error, Slice};
+use alloc::vec::Vec;
+use core::ptr;
+
+impl Slice {
+    /// Appends copies of the elements that match the slice expression to `dst`.
+    ///
+    /// This is a tight gather loop meant for hot paths: `dst` is grown at most once and can be
+    /// reused across calls to avoid allocations.
+    pub fn apply_copy_into<T>(&self, src: &[T], dst: &mut Vec<T>)
+    where
+        T: Copy,
+    {
+        let it = self.indices(src.len());
+        dst.reserve(it.len());
+        let mut i = it.next;
+        for _ in 0..it.len() {
+            dst.push(src[i]);
+            i = i.wrapping_add(it.delta);
+        }
+    }
+
+    /// Replaces the contents of `out` with references to the elements that match the slice
+    /// expression.
+    ///
+    /// Unlike `apply_to_vec`, this reuses the allocation of `out`, so applying many slices in a
+    /// loop only allocates when a selection outgrows all the previous ones.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let frame = [1, 2, 3, 4, 5];
+    /// let mut out = Vec::new();
+    /// for expr in ["[::2]", "[-2::]"] {
+    ///     let s: Slice = expr.parse()?;
+    ///     s.select_into(&frame, &mut out);
+    /// }
+    /// assert_eq!(out, vec![&4, &5]);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn select_into<'a, T>(&self, src: &'a [T], out: &mut Vec<&'a T>) {
+        out.clear();
+        let it = self.indices(src.len());
+        out.reserve(it.len());
+        out.extend(it.map(|i| &src[i]));
+    }
+
+    /// Replaces the contents of `out` with clones of the elements that match the slice
+    /// expression, reusing its allocation.
+    pub fn select_cloned_into<T>(&self, src: &[T], out: &mut Vec<T>)
+    where
+        T: Clone,
+    {
+        out.clear();
+        let it = self.indices(src.len());
+        out.reserve(it.len());
+        out.extend(it.map(|i| src[i].clone()));
+    }
+
+    /// Replaces the contents of `out` with copies of the elements that match the slice
+    /// expression, reusing its allocation.
+    pub fn select_copied_into<T>(&self, src: &[T], out: &mut Vec<T>)
+    where
+        T: Copy,
+    {
+        out.clear();
+        self.apply_copy_into(src, out);
+    }
+
+    /// Returns a vector with references to the elements that match the slice expression.
+    pub fn apply_to_vec<'a, T>(&self, arr: &'a [T]) -> Vec<&'a T> {
+        let it = self.indices(arr.len());
+        let mut v = Vec::with_capacity(it.len());
+        v.extend(it.map(|i| &arr[i]));
+        v
+    }
+
+    /// Splits `arr` into the elements that match the slice expression, in selection order, and
+    /// the other elements, in their original order, walking `arr` only once.
+    ///
+    /// # Example
+    /// ```
+    /// use slyce::Slice;
+    /// let s: Slice = "[::-2]".parse()?;
+    /// let (selected, rest) = s.partition(&[1, 2, 3, 4, 5]);
+    /// assert_eq!(selected, vec![&5, &3, &1]);
+    /// assert_eq!(rest, vec![&2, &4]);
+    /// # Ok::<(), slyce::ParseError>(())
+    /// ```
+    pub fn partition<'a, T>(&self, arr: &'a [T]) -> (Vec<&'a T>, Vec<&'a T>) {
+        let it = self.indices(arr.len());
+        let mut selected = Vec::with_capacity(it.len());
+        let mut rest = Vec::with_capacity(arr.len() - it.len());
+        for (i, x) in arr.iter().enumerate() {
+            if it.contains(i) {
+                selected.push(x);
+            } else {
+                rest.push(x);
+            }
+        }
+        if !it.forward {
+            selected.reverse();
+        }
+        (selected, rest)
+    }
+
+    /// Returns a vector with clones of the elements that match the slice expression.
+    pub fn apply_to_vec_cloned<T>(&self, arr: &[T]) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let it = self.indices(arr.len());
+        let mut v = Vec::with_capacity(it.len());
+        v.extend(it.map(|i| arr[i].clone()));
+        v
+    }
+
+    /// Replaces the selected elements of `v` with the elements of `src`, like Python's
+    /// `v[slice] = src`.
+    ///
+    /// When the step is `1` or `-1` the selection is a contiguous range, which is replaced by
+    /// `src` (reversed if the step is negative); the length of `v` can change.
+    /// For any other step the number of elements in `src` must match the number of selected
+    /// elements, otherwise `Error::LengthMismatch` is returned and `v` is left untouched.
+    pub fn assign<T, I>(&self, v: &mut Vec<T>, src: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.try_splice(v, src).map(drop)
+    }
+
+    /// Replaces the selected elements of `v` with the elements of `replace_with` and returns the
+    /// removed elements in selection order.
+    ///
+    /// This mirrors `Vec::splice`, but accepts a slice instead of a range. The same rules as
+    /// `assign` apply to the replacement.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step is neither `1` nor `-1` and the number of elements in `replace_with`
+    /// doesn't match the number of selected elements.
+    pub fn splice<T, I>(&self, v: &mut Vec<T>, replace_with: I) -> Vec<T>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.try_splice(v, replace_with)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_splice<T, I>(&self, v: &mut Vec<T>, src: I) -> Result<Vec<T>, Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let (start, end, _) = self.bounds(v.len());
+        let it = self.indices(v.len());
+        match self.step.unwrap_or(1) {
+            1 => {
+                let (lo, hi) = (start, end.max(start));
+                Ok(v.splice(lo..hi, src).collect())
+            }
+            -1 => {
+                let (lo, hi) = (end, start.max(end));
+                let mut src: Vec<T> = src.into_iter().collect();
+                src.reverse();
+                let mut removed: Vec<T> = v.splice(lo..hi, src).collect();
+                removed.reverse();
+                Ok(removed)
+            }
+            _ => {
+                let src: Vec<T> = src.into_iter().collect();
+                let expected = it.clone().count();
+                if expected != src.len() {
+                    return Err(Error::LengthMismatch {
+                        expected,
+                        actual: src.len(),
+                    });
+                }
+                Ok(it
+                    .zip(src)
+                    .map(|(i, x)| core::mem::replace(&mut v[i], x))
+                    .collect())
+            }
+        }
+    }
+
+    /// Removes the selected elements from `v`, like Python's `del v[slice]`.
+    ///
+    /// The remaining elements keep their relative order. This runs in a single pass over `v`.
+    pub fn delete<T>(&self, v: &mut Vec<T>) {
+        let it = self.indices(v.len());
+        let mut i = 0;
+        v.retain(|_| {
+            let keep = !it.contains(i);
+            i += 1;
+            keep
+        });
+    }
+
+    /// Removes the selected elements from `v` and returns them in selection order.
+    ///
+    /// This is like `Vec::drain`, but it supports steps and negative directions. The remaining
+    /// elements are compacted in place, in a single pass over `v`.
+    pub fn extract<T>(&self, v: &mut Vec<T>) -> Vec<T> {
+        let it = self.indices(v.len());
+        let len = v.len();
+        let mut taken = Vec::with_capacity(it.len());
+        let mut kept = 0;
+        let p = v.as_mut_ptr();
+        // SAFETY: the length of `v` is zero while its elements are moved, so a panic can't drop
+        // them twice; nothing in the loop can panic anyway, since `taken` has room for all the
+        // selected elements. Each element is read once, either into `taken` or into position
+        // `kept`, which is never past it, and `v` ends up owning the first `kept` positions.
+        unsafe {
+            v.set_len(0);
+            for i in 0..len {
+                if it.contains(i) {
+                    taken.push(ptr::read(p.add(i)));
+                } else {
+                    ptr::copy(p.add(i), p.add(kept), 1);
+                    kept += 1;
+                }
+            }
+            v.set_len(kept);
+        }
+        if !it.forward {
+            taken.reverse();
+        }
+        taken
+    }
+
+    /// Keeps only the selected elements of `v`, in selection order, dropping the rest.
+    ///
+    /// This is the inverse of `delete`; it reuses the allocation of `v`.
+    pub fn retain_selected<T>(&self, v: &mut Vec<T>) {
+        let it = self.indices(v.len());
+        let mut i = 0;
+        v.retain(|_| {
+            let keep = it.contains(i);
+            i += 1;
+            keep
+        });
+        if !it.forward {
+            v.reverse();
+        }
+    }
+
+    /// Consumes `v` and returns an iterator that yields the selected elements by value.
+    ///
+    /// Unselected elements are dropped; no element is cloned.
+    pub fn apply_into<T>(&self, mut v: Vec<T>) -> impl Iterator<Item = T> {
+        self.retain_selected(&mut v);
+        v.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Error, Index, Slice};
+
+    fn s(start: Option<isize>, end: Option<isize>, step: Option<isize>) -> Slice {
+        let (start, end) = (start.into(), end.into());
+        Slice { start, end, step }
+    }
+
+    #[test]
+    fn apply_copy_into() {
+        let v = vec![10, 20, 30, 40, 50];
+        let s = Slice {
+            start: Index::Default,
+            end: Index::Head(0),
+            step: Some(-2),
+        };
+        let mut dst = vec![1];
+        s.apply_copy_into(&v, &mut dst);
+        assert_eq!(dst, vec![1, 50, 30]);
+        s.apply_copy_into(&v, &mut dst);
+        assert_eq!(dst, vec![1, 50, 30, 50, 30]);
+    }
+
+    #[test]
+    fn apply_to_vec() {
+        let v = vec![10, 20, 30, 40, 50];
+        let s = Slice {
+            start: Index::Head(1),
+            end: Index::Default,
+            step: Some(2),
+        };
+        assert_eq!(s.apply_to_vec(&v), vec![&20, &40]);
+        assert_eq!(s.apply_to_vec_cloned(&v), vec![20, 40]);
+    }
+
+    #[test]
+    fn select_into() {
+        let v = vec![10, 20, 30, 40, 50];
+        let mut refs = vec![&0; 8];
+        let (mut cloned, mut copied) = (vec![0; 8], vec![0; 8]);
+        for (expr, want) in [("[::-2]", vec![50, 30, 10]), ("[1:2:]", vec![20])] {
+            let s: Slice = expr.parse().unwrap();
+            s.select_into(&v, &mut refs);
+            s.select_cloned_into(&v, &mut cloned);
+            s.select_copied_into(&v, &mut copied);
+            assert_eq!(refs.iter().map(|&&x| x).collect::<Vec<_>>(), want);
+            assert_eq!(cloned, want);
+            assert_eq!(copied, want);
+        }
+        assert!(refs.capacity() >= 8);
+    }
+
+    #[test]
+    fn partition() {
+        let v = [0, 1, 2, 3, 4, 5];
+        let split = |expr: &str| {
+            let (selected, rest) = expr.parse::<Slice>().unwrap().partition(&v);
+            let copied = |x: Vec<&i32>| x.into_iter().copied().collect::<Vec<_>>();
+            (copied(selected), copied(rest))
+        };
+        assert_eq!(split("[::]"), (vec![0, 1, 2, 3, 4, 5], vec![]));
+        assert_eq!(split("[1:4:]"), (vec![1, 2, 3], vec![0, 4, 5]));
+        assert_eq!(split("[::2]"), (vec![0, 2, 4], vec![1, 3, 5]));
+        // the selection keeps its order, the rest is in array order.
+        assert_eq!(split("[::-2]"), (vec![5, 3, 1], vec![0, 2, 4]));
+        assert_eq!(split("[-2:0:-3]"), (vec![4, 1], vec![0, 2, 3, 5]));
+        assert_eq!(split("[4:1:]"), (vec![], vec![0, 1, 2, 3, 4, 5]));
+        assert_eq!(split("[::0]"), (vec![], vec![0, 1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn assign() -> Result<(), Error> {
+        let mut v = vec![0, 1, 2, 3, 4];
+        s(Some(1), Some(3), None).assign(&mut v, vec![10, 11, 12])?;
+        assert_eq!(v, vec![0, 10, 11, 12, 3, 4]);
+
+        let mut v = vec![0, 1, 2, 3, 4];
+        s(Some(3), Some(1), None).assign(&mut v, vec![10])?;
+        assert_eq!(v, vec![0, 1, 2, 10, 3, 4]);
+
+        let mut v = vec![0, 1, 2, 3, 4];
+        s(Some(-2), None, None).assign(&mut v, vec![])?;
+        assert_eq!(v, vec![0, 1, 2]);
+
+        let mut v = vec![0, 1, 2, 3, 4];
+        s(Some(3), Some(0), Some(-1)).assign(&mut v, vec![10, 11])?;
+        assert_eq!(v, vec![0, 11, 10, 4]);
+
+        let mut v = vec![0, 1, 2, 3, 4];
+        s(None, None, Some(-2)).assign(&mut v, vec![10, 11, 12])?;
+        assert_eq!(v, vec![12, 1, 11, 3, 10]);
+
+        let mut v = vec![0, 1, 2, 3, 4];
+        assert_eq!(
+            s(None, None, Some(2)).assign(&mut v, vec![10]),
+            Err(Error::LengthMismatch {
+                expected: 3,
+                actual: 1
+            })
+        );
+        assert_eq!(v, vec![0, 1, 2, 3, 4]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn delete() {
+        let del = |s: Slice| {
+            let mut v = vec![0, 1, 2, 3, 4, 5];
+            s.delete(&mut v);
+            v
+        };
+
+        assert_eq!(del(s(None, None, None)), Vec::<i32>::new());
+        assert_eq!(del(s(Some(1), Some(-1), None)), vec![0, 5]);
+        assert_eq!(del(s(None, None, Some(2))), vec![1, 3, 5]);
+        assert_eq!(del(s(None, None, Some(-2))), vec![0, 2, 4]);
+        assert_eq!(del(s(Some(-2), Some(0), Some(-3))), vec![0, 2, 3, 5]);
+        assert_eq!(del(s(Some(3), Some(1), None)), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(del(s(None, None, Some(0))), vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn extract() {
+        let ext = |s: Slice| {
+            let mut v = vec![0, 1, 2, 3, 4, 5];
+            let taken = s.extract(&mut v);
+            (taken, v)
+        };
+
+        assert_eq!(
+            ext(s(None, None, None)),
+            (vec![0, 1, 2, 3, 4, 5], Vec::<i32>::new())
+        );
+        assert_eq!(
+            ext(s(Some(1), Some(-1), None)),
+            (vec![1, 2, 3, 4], vec![0, 5])
+        );
+        assert_eq!(ext(s(None, None, Some(2))), (vec![0, 2, 4], vec![1, 3, 5]));
+        assert_eq!(ext(s(None, None, Some(-2))), (vec![5, 3, 1], vec![0, 2, 4]));
+        assert_eq!(
+            ext(s(None, None, Some(0))),
+            (vec![], vec![0, 1, 2, 3, 4, 5])
+        );
+
+        // zero-sized types have a capacity of `usize::MAX`.
+        let mut v = vec![(); 5];
+        assert_eq!(s(None, None, Some(2)).extract(&mut v), vec![(); 3]);
+        assert_eq!(v, vec![(); 2]);
+
+        // elements that own memory are moved, not copied.
+        let mut v: Vec<String> = (0..5).map(|i| i.to_string()).collect();
+        assert_eq!(s(Some(-1), None, Some(-3)).extract(&mut v), vec!["4", "1"]);
+        assert_eq!(v, vec!["0", "2", "3"]);
+    }
+
+    #[test]
+    fn retain_selected() {
+        let ret = |s: Slice| {
+            let mut v = vec![0, 1, 2, 3, 4, 5];
+            s.retain_selected(&mut v);
+            v
+        };
+
+        assert_eq!(ret(s(None, None, None)), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(ret(s(Some(1), Some(-1), None)), vec![1, 2, 3, 4]);
+        assert_eq!(ret(s(None, None, Some(2))), vec![0, 2, 4]);
+        assert_eq!(ret(s(None, None, Some(-2))), vec![5, 3, 1]);
+        assert_eq!(ret(s(None, None, Some(0))), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn splice() {
+        let mut v = vec![0, 1, 2, 3, 4];
+        assert_eq!(s(Some(-2), None, None).splice(&mut v, vec![10]), vec![3, 4]);
+        assert_eq!(v, vec![0, 1, 2, 10]);
+
+        let mut v = vec![0, 1, 2, 3, 4];
+        assert_eq!(
+            s(Some(3), Some(0), Some(-1)).splice(&mut v, vec![]),
+            vec![3, 2, 1]
+        );
+        assert_eq!(v, vec![0, 4]);
+
+        let mut v = vec![0, 1, 2, 3, 4];
+        assert_eq!(
+            s(None, None, Some(-2)).splice(&mut v, vec![10, 11, 12]),
+            vec![4, 2, 0]
+        );
+        assert_eq!(v, vec![12, 1, 11, 3, 10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "length mismatch: expected 3 elements, found 1")]
+    fn splice_length_mismatch() {
+        let mut v = vec![0, 1, 2, 3, 4];
+        s(None, None, Some(2)).splice(&mut v, vec![10]);
+    }
+
+    #[test]
+    fn apply_into() {
+        let v: Vec<String> = vec!["a", "b", "c", "d"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let r: Vec<String> = s(Some(-1), None, Some(-2)).apply_into(v).collect();
+        assert_eq!(r, vec!["d", "b"]);
+    }
+}
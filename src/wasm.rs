@@ -0,0 +1,89 @@
+//! JavaScript bindings, for use with `wasm-bindgen`.
+
+use crate::Slice;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+use js_sys::{Array, Reflect};
+use wasm_bindgen::prelude::*;
+
+fn parse(expr: &str) -> Result<Slice, JsValue> {
+    expr.parse()
+        .map_err(|e: crate::ParseError| js_sys::SyntaxError::new(&e.to_string()).into())
+}
+
+/// Returns the indices selected by a slice expression in a sequence of `len` elements.
+#[wasm_bindgen]
+pub fn indices(expr: &str, len: usize) -> Result<Vec<u32>, JsValue> {
+    Ok(parse(expr)?.indices(len).map(|i| i as u32).collect())
+}
+
+/// Returns a new array holding the elements of `array` selected by a slice expression.
+///
+/// `array` can be anything with a `length` and integer-indexed elements, such as an `Array` or a
+/// typed array.
+#[wasm_bindgen]
+pub fn apply(expr: &str, array: &JsValue) -> Result<Array, JsValue> {
+    let slice = parse(expr)?;
+    let len = Reflect::get(array, &"length".into())?
+        .as_f64()
+        .ok_or_else(|| js_sys::TypeError::new("expected an array-like value"))?;
+    slice
+        .indices(len as usize)
+        .map(|i| Reflect::get_u32(array, i as u32))
+        .collect()
+}
+
+/// Describes in words what a slice expression selects in a sequence of `len` elements.
+#[wasm_bindgen]
+pub fn explain(expr: &str, len: usize) -> Result<String, JsValue> {
+    Ok(describe(&parse(expr)?, len))
+}
+
+fn describe(slice: &Slice, len: usize) -> String {
+    let mut s = String::new();
+    let step = slice.step.unwrap_or(1);
+    let _ = write!(s, "{} on {} elements", slice, len);
+    let n = slice.count(len);
+    if n == 0 {
+        s.push_str(" selects nothing");
+        return s;
+    }
+    let first = slice.nth(len, 0).unwrap();
+    let _ = write!(
+        s,
+        " selects {} element{} starting at index {}",
+        n,
+        if n == 1 { "" } else { "s" },
+        first
+    );
+    if n > 1 {
+        let direction = if step > 0 { "forward" } else { "backward" };
+        let _ = write!(s, ", moving {} by {}", direction, step.unsigned_abs());
+        let _ = write!(s, " up to index {}", slice.nth(len, n - 1).unwrap());
+    }
+    s
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn describe() {
+        let d = |expr: &str, len| super::describe(&expr.parse().unwrap(), len);
+        assert_eq!(
+            d("[1:-1:2]", 6),
+            "[1:-1:2] on 6 elements selects 2 elements starting at index 1, \
+             moving forward by 2 up to index 3"
+        );
+        assert_eq!(
+            d("[::-1]", 3),
+            "[::-1] on 3 elements selects 3 elements starting at index 2, \
+             moving backward by 1 up to index 0"
+        );
+        assert_eq!(
+            d("[-1::]", 3),
+            "[-1::] on 3 elements selects 1 element starting at index 2"
+        );
+        assert_eq!(d("[5::]", 3), "[5::] on 3 elements selects nothing");
+    }
+}